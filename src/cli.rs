@@ -0,0 +1,330 @@
+use std::{
+    fs, process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use sudoku::{
+    batch::{Batch, Outcome, Task},
+    generator::{Generator, Symmetry},
+    grade::Difficulty,
+    logic::LogicalSolver,
+    Sudoku, SudokuParseError,
+};
+
+/// Subcommands which take a FILE, so that a FILE with the same name has to be
+/// opened with `sudoku gui FILE`
+pub const COMMANDS: [&str; 7] = [
+    "solve", "check", "print", "explain", "grade", "generate", "batch",
+];
+
+pub const USAGE: &str = "\
+usage: sudoku [gui] [FILE]
+       sudoku solve FILE
+       sudoku check FILE
+       sudoku print FILE
+       sudoku explain FILE
+       sudoku grade FILE
+       sudoku generate [--seed N] [--symmetry SYMMETRY] [--difficulty DIFFICULTY]
+       sudoku batch solve|count|grade FILE
+
+commands:
+    gui      open FILE in the interactive solver (default, FILE defaults to sudoku.txt)
+    solve    print a solution of the puzzle in FILE
+    check    report whether the puzzle in FILE has exactly one solution
+    print    print the puzzle in FILE
+    explain  solve the puzzle in FILE step by step, explaining each deduction
+    grade    rate the difficulty of the puzzle in FILE
+    generate print a new puzzle with a unique solution
+             SYMMETRY is one of none, rotational, mirror or diagonal
+             DIFFICULTY is one of easy, medium, hard or expert
+    batch    solve, count the solutions of (0, 1 or 2 for more than one) or grade
+             every puzzle in FILE, which holds one puzzle per line, printing
+             the line number before each result
+
+exit status:
+    0    success
+    1    the puzzle has no solution
+    2    the puzzle has more than one solution
+    3    invalid arguments
+    4    FILE could not be read
+    5    the board in FILE does not have 81 cells
+    6    FILE contains an invalid character
+    7    the givens in FILE conflict with each other
+    8    the puzzle can not be solved without guessing
+    9    no puzzle of the requested difficulty was found";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Success = 0,
+    Unsolvable = 1,
+    Ambiguous = 2,
+    Usage = 3,
+    Unreadable = 4,
+    InvalidSize = 5,
+    InvalidChar = 6,
+    InvalidSudoku = 7,
+    Stuck = 8,
+    NotGenerated = 9,
+}
+
+impl Status {
+    pub fn exit(self) -> ! {
+        process::exit(self as i32)
+    }
+}
+
+impl From<SudokuParseError> for Status {
+    fn from(error: SudokuParseError) -> Self {
+        match error {
+            SudokuParseError::TooShort(_) | SudokuParseError::TooLong(_) => Status::InvalidSize,
+            SudokuParseError::InvalidChar(_, _) => Status::InvalidChar,
+            SudokuParseError::InvalidSudoku(_) => Status::InvalidSudoku,
+        }
+    }
+}
+
+pub fn load(path: &str) -> Result<Sudoku, Status> {
+    let contents = fs::read_to_string(path).map_err(|e| {
+        eprintln!("could not read {}: {}", path, e);
+
+        Status::Unreadable
+    })?;
+
+    contents.parse().map_err(|e: SudokuParseError| {
+        match e.location() {
+            Some(location) => {
+                eprintln!("{}:{}: {}", path, location, e);
+                eprintln!("{}", location.caret(&contents));
+            }
+            None => eprintln!("{}: {}", path, e),
+        }
+
+        if let SudokuParseError::InvalidSudoku(conflicts) = &e {
+            for conflict in conflicts {
+                eprintln!("    {}", conflict);
+            }
+        }
+
+        Status::from(e)
+    })
+}
+
+pub fn solve(path: &str) -> Status {
+    let sudoku = match load(path) {
+        Ok(sudoku) => sudoku,
+        Err(status) => return status,
+    };
+
+    match sudoku.solve() {
+        Some(solution) => {
+            println!("{}", solution);
+
+            Status::Success
+        }
+        None => {
+            eprintln!("{}: the puzzle has no solution", path);
+
+            Status::Unsolvable
+        }
+    }
+}
+
+pub fn check(path: &str) -> Status {
+    let sudoku = match load(path) {
+        Ok(sudoku) => sudoku,
+        Err(status) => return status,
+    };
+
+    match sudoku.count_solutions(2) {
+        0 => {
+            println!("{}: unsolvable", path);
+
+            Status::Unsolvable
+        }
+        1 => {
+            println!("{}: proper", path);
+
+            Status::Success
+        }
+        _ => {
+            println!("{}: ambiguous", path);
+
+            Status::Ambiguous
+        }
+    }
+}
+
+pub fn print(path: &str) -> Status {
+    match load(path) {
+        Ok(sudoku) => {
+            println!("{}", sudoku);
+
+            Status::Success
+        }
+        Err(status) => status,
+    }
+}
+
+pub fn explain(path: &str) -> Status {
+    let mut solver = match load(path) {
+        Ok(sudoku) => LogicalSolver::new(sudoku),
+        Err(status) => return status,
+    };
+
+    while let Some(deduction) = solver.step() {
+        println!("{}", deduction);
+    }
+
+    println!();
+    println!("{}", solver.sudoku());
+
+    if solver.is_solved() {
+        Status::Success
+    } else {
+        eprintln!("{}: no technique makes any more progress", path);
+
+        Status::Stuck
+    }
+}
+
+pub fn grade(path: &str) -> Status {
+    let sudoku = match load(path) {
+        Ok(sudoku) => sudoku,
+        Err(status) => return status,
+    };
+
+    match sudoku.grade() {
+        Some(grade) => {
+            print!("{}", grade);
+
+            Status::Success
+        }
+        None if sudoku.count_solutions(2) == 0 => {
+            eprintln!("{}: the puzzle has no solution", path);
+
+            Status::Unsolvable
+        }
+        None => {
+            eprintln!("{}: the puzzle has more than one solution", path);
+
+            Status::Ambiguous
+        }
+    }
+}
+
+pub fn generate(options: &[&str]) -> Status {
+    let mut seed = None;
+    let mut symmetry = Symmetry::None;
+    let mut difficulty = None;
+
+    for option in options.chunks(2) {
+        let parsed = match option {
+            ["--seed", value] => value.parse().map(|value| seed = Some(value)).ok(),
+            ["--symmetry", value] => Symmetry::ALL
+                .iter()
+                .find(|symmetry| symmetry.to_string() == *value)
+                .map(|&value| symmetry = value),
+            ["--difficulty", value] => Difficulty::ALL
+                .iter()
+                .find(|difficulty| difficulty.to_string().to_lowercase() == *value)
+                .map(|&value| difficulty = Some(value)),
+            _ => None,
+        };
+
+        if parsed.is_none() {
+            eprintln!("{}", USAGE);
+
+            return Status::Usage;
+        }
+    }
+
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64)
+    });
+    eprintln!("seed: {}", seed);
+
+    let mut generator = Generator::new(seed).symmetry(symmetry);
+    if let Some(difficulty) = difficulty {
+        generator = generator.difficulty(difficulty);
+    }
+
+    match generator.generate() {
+        Some(puzzle) => {
+            println!("{}", puzzle);
+
+            Status::Success
+        }
+        None => {
+            eprintln!("could not generate a puzzle of the requested difficulty");
+
+            Status::NotGenerated
+        }
+    }
+}
+
+pub fn batch(task: &str, path: &str) -> Status {
+    let task = match task {
+        "solve" => Task::Solve,
+        "count" => Task::Count(2),
+        "grade" => Task::Grade,
+        _ => {
+            eprintln!("{}", USAGE);
+
+            return Status::Usage;
+        }
+    };
+
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("could not read {}: {}", path, e);
+
+            return Status::Unreadable;
+        }
+    };
+
+    let batch = Batch::run(&contents, task);
+
+    // Every result starts with its line number, as blank lines and comments
+    // are skipped
+    for entry in batch.entries.iter() {
+        match &entry.outcome {
+            Ok(Outcome::Solved(Some(solution))) => {
+                println!("{}: {}", entry.line, solution.to_line_string())
+            }
+            Ok(Outcome::Solved(None)) => println!("{}: unsolvable", entry.line),
+            Ok(Outcome::Counted(count)) => println!("{}: {}", entry.line, count),
+            Ok(Outcome::Graded(Some(grade))) => {
+                println!("{}: {:.1} {}", entry.line, grade.rating, grade.difficulty)
+            }
+            Ok(Outcome::Graded(None)) => println!("{}: not unique", entry.line),
+            Err(e) => {
+                println!("{}: unreadable", entry.line);
+
+                match e.location() {
+                    Some(location) => {
+                        eprintln!("{}:{}:{}: {}", path, entry.line, location.column, e)
+                    }
+                    None => eprintln!("{}:{}: {}", path, entry.line, e),
+                }
+            }
+        }
+    }
+
+    eprintln!(
+        "{} puzzles in {:.3}s ({:.0} puzzles/s), {} unreadable",
+        batch.entries.len(),
+        batch.elapsed.as_secs_f64(),
+        batch.puzzles_per_second(),
+        batch.errors().count()
+    );
+
+    let status = match batch.errors().next() {
+        Some((_, e)) => Status::from(e.clone()),
+        None => Status::Success,
+    };
+
+    status
+}
@@ -1,6 +1,6 @@
 use cairo::Context;
 
-use crate::sudoku::Digit;
+use sudoku::{CellOrigin, Digit};
 
 pub fn rgb(hex: u32) -> RGB {
     assert!(hex <= 0xffffff);
@@ -28,6 +28,7 @@ pub trait SetColor<T> {
     fn set_color(&self, color: T);
 }
 
+#[allow(clippy::upper_case_acronyms)]
 pub struct RGB {
     red: f64,
     green: f64,
@@ -40,6 +41,7 @@ impl SetColor<RGB> for Context {
     }
 }
 
+#[allow(clippy::upper_case_acronyms)]
 pub struct RGBA {
     red: f64,
     green: f64,
@@ -69,3 +71,12 @@ pub fn get_digit_color(digit: Digit) -> RGB {
         Digit::Five => rgb(0xf9b0b4),
     }
 }
+
+pub fn get_origin_color(origin: CellOrigin) -> RGB {
+    match origin {
+        CellOrigin::Given | CellOrigin::Imported => rgb(0x000000),
+        CellOrigin::Player => rgb(0x1f4e9c),
+        CellOrigin::Solver => rgb(0x606060),
+        CellOrigin::Hint => rgb(0x9c27b0),
+    }
+}
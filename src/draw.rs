@@ -1,10 +1,15 @@
 use cairo::{Context, Matrix, Rectangle};
 
+use sudoku::{Cell, CellOrigin, CellValue, House, Position, SolveStatus, Sudoku};
+
 use crate::{
-    color::{get_digit_color, rgb, rgba, SetColor},
-    sudoku::{Cell, CellValue, Sudoku},
+    color::{get_digit_color, get_origin_color, rgb, rgba, SetColor},
+    selection::Selection,
 };
 
+const BLOCK_BORDER_WIDTH: f64 = 0.02;
+const CELL_BORDER_WIDTH: f64 = 0.01;
+
 pub trait Drawable {
     fn draw(&self, ctx: &Context, bounds: Rectangle) {
         ctx.save();
@@ -28,82 +33,148 @@ pub trait Drawable {
     fn draw_impl(&self, ctx: &Context);
 }
 
-impl Drawable for Sudoku {
-    fn draw_impl(&self, ctx: &Context) {
-        let border_width = 0.02;
+/// The bounds of the `i`th square of a 3x3 grid in a unit square, in reading
+/// order, leaving `border_width` between squares
+fn grid_bounds(i: usize, border_width: f64) -> Rectangle {
+    let x = (i % 3) as f64;
+    let y = (i / 3) as f64;
+
+    Rectangle {
+        x: x / 3.0 + (border_width * x) / 2.0,
+        y: y / 3.0 + (border_width * y) / 2.0,
+        width: 1.0 / 3.0 - (border_width * 2.0) / 2.0,
+        height: 1.0 / 3.0 - (border_width * 2.0) / 2.0,
+    }
+}
+
+/// The square of a 3x3 grid a point of a unit square is in, along with the
+/// point relative to that square
+fn grid_square_at(x: f64, y: f64, border_width: f64) -> Option<(usize, f64, f64)> {
+    (0..9).find_map(|i| {
+        let bounds = grid_bounds(i, border_width);
+        let x = (x - bounds.x) / bounds.width;
+        let y = (y - bounds.y) / bounds.height;
+
+        if (0.0..1.0).contains(&x) && (0.0..1.0).contains(&y) {
+            Some((i, x, y))
+        } else {
+            None
+        }
+    })
+}
+
+/// The cell under a point of the unit square a [`Board`] is drawn in, or
+/// `None` for the borders between cells
+pub fn position_at(x: f64, y: f64) -> Option<Position> {
+    let (block, x, y) = grid_square_at(x, y, BLOCK_BORDER_WIDTH)?;
+    let (cell, _, _) = grid_square_at(x, y, CELL_BORDER_WIDTH)?;
+
+    Position::in_block(block, cell)
+}
+
+/// The bounds of a cell in the unit square a [`Board`] is drawn in
+fn cell_bounds(position: Position) -> Rectangle {
+    let block = grid_bounds(position.block(), BLOCK_BORDER_WIDTH);
+    let cell = grid_bounds(
+        (position.row() % 3) * 3 + position.column() % 3,
+        CELL_BORDER_WIDTH,
+    );
+
+    Rectangle {
+        x: block.x + cell.x * block.width,
+        y: block.y + cell.y * block.height,
+        width: cell.width * block.width,
+        height: cell.height * block.height,
+    }
+}
+
+/// The bounds of a house, from its first cell to its last
+fn house_bounds(house: House) -> Rectangle {
+    let mut cells = house.cells().map(cell_bounds);
+    let first = cells.next().expect("houses have 9 cells");
+    let last = cells.last().expect("houses have 9 cells");
+
+    Rectangle {
+        x: first.x,
+        y: first.y,
+        width: last.x + last.width - first.x,
+        height: last.y + last.height - first.y,
+    }
+}
+
+/// The board along with the cells the player has selected
+pub struct Board<'s> {
+    pub sudoku: &'s Sudoku,
+    pub selection: &'s Selection,
+}
 
+impl Drawable for Board<'_> {
+    fn draw_impl(&self, ctx: &Context) {
         for i in 0..9 {
-            let x = i % 3;
-            let y = i / 3;
-
-            DrawingBlock { sudoku: self, x, y }.draw(
-                ctx,
-                Rectangle {
-                    x: x as f64 / 3.0 + (border_width * x as f64) / 2.0,
-                    y: y as f64 / 3.0 + (border_width * y as f64) / 2.0,
-                    width: 1.0 / 3.0 - (border_width * 2.0) / 2.0,
-                    height: 1.0 / 3.0 - (border_width * 2.0) / 2.0,
-                },
-            );
+            DrawingBlock {
+                board: self,
+                x: i % 3,
+                y: i / 3,
+            }
+            .draw(ctx, grid_bounds(i, BLOCK_BORDER_WIDTH));
+        }
+
+        // Completed houses are shaded green, houses with a digit twice red
+        for house in House::all() {
+            match self.sudoku.house_status(house) {
+                SolveStatus::Unsolved => continue,
+                SolveStatus::Solved => ctx.set_color(rgba(0x00ff0030)),
+                SolveStatus::Invalid => ctx.set_color(rgba(0xff000030)),
+            }
+
+            let bounds = house_bounds(house);
+            ctx.rectangle(bounds.x, bounds.y, bounds.width, bounds.height);
+            ctx.fill();
         }
     }
 }
 
 struct DrawingBlock<'s> {
-    sudoku: &'s Sudoku,
+    board: &'s Board<'s>,
     x: usize,
     y: usize,
 }
 impl<'s> Drawable for DrawingBlock<'s> {
     fn draw_impl(&self, ctx: &Context) {
-        let border_width = 0.01;
-
         for i in 0..9 {
-            let x = i % 3;
-            let y = i / 3;
-
-            let global_x = x + self.x * 3;
-            let global_y = y + self.y * 3;
+            let position =
+                Position::in_block(self.x + self.y * 3, i).expect("block is on the board");
 
             DrawingCell {
-                contents: self.sudoku.get((global_x, global_y)),
-                sudoku: self.sudoku,
-                x: global_x,
-                y: global_y,
+                contents: self.board.sudoku.get(position),
+                sudoku: self.board.sudoku,
+                position,
+                is_selected: self.board.selection.contains(position),
             }
-            .draw(
-                ctx,
-                Rectangle {
-                    x: x as f64 / 3.0 + (border_width * x as f64) / 2.0,
-                    y: y as f64 / 3.0 + (border_width * y as f64) / 2.0,
-                    width: 1.0 / 3.0 - (border_width * 2.0) / 2.0,
-                    height: 1.0 / 3.0 - (border_width * 2.0) / 2.0,
-                },
-            );
+            .draw(ctx, grid_bounds(i, CELL_BORDER_WIDTH));
         }
-
-        // match self.sudoku.block_status((self.x, self.y)) {
-        //     SolveStatus::Unsolved => {}
-        //     SolveStatus::Solved => {
-        //         ctx.set_color(rgba(0x00ff0050));
-        //         ctx.rectangle(0.0, 0.0, 1.0, 1.0);
-        //         ctx.fill();
-        //     }
-        //     SolveStatus::Invalid => {
-        //         ctx.set_color(rgba(0xff000050));
-        //         ctx.rectangle(0.0, 0.0, 1.0, 1.0);
-        //         ctx.fill();
-        //     }
-        // }
     }
 }
 
+/// Where the corner marks of a cell go, as `(column, row)` in a 3x3 grid
+const CORNER_SLOTS: [(usize, usize); 9] = [
+    (0, 0),
+    (2, 0),
+    (0, 2),
+    (2, 2),
+    (1, 0),
+    (1, 2),
+    (0, 1),
+    (2, 1),
+    (1, 1),
+];
+
 #[derive(Debug, Clone)]
 struct DrawingCell<'s> {
     sudoku: &'s Sudoku,
     contents: CellValue<'s>,
-    x: usize,
-    y: usize,
+    position: Position,
+    is_selected: bool,
 }
 impl Drawable for DrawingCell<'_> {
     fn draw_impl(&self, ctx: &Context) {
@@ -114,20 +185,21 @@ impl Drawable for DrawingCell<'_> {
         match &self.contents {
             CellValue::Known(Cell {
                 value: digit,
-                is_given,
+                origin,
+                ..
             }) => {
-                if *is_given {
+                if *origin == CellOrigin::Given {
                     ctx.set_color(get_digit_color(*digit));
                     ctx.rectangle(0.0, 0.0, 1.0, 1.0);
                     ctx.fill();
                 }
 
                 ctx.set_font_size(0.8);
-                ctx.set_color(rgb(match self.sudoku.cell_status((self.x, self.y)) {
-                    crate::sudoku::SolveStatus::Unsolved => 0x000000,
-                    crate::sudoku::SolveStatus::Solved => 0x00ff00,
-                    crate::sudoku::SolveStatus::Invalid => 0xff0000,
-                }));
+                ctx.set_color(match self.sudoku.cell_status(self.position) {
+                    SolveStatus::Unsolved => get_origin_color(*origin),
+                    SolveStatus::Solved => rgb(0x00ff00),
+                    SolveStatus::Invalid => rgb(0xff0000),
+                });
 
                 let digit = u8::from(*digit).to_string();
                 let text_extents = ctx.text_extents(&digit);
@@ -138,18 +210,17 @@ impl Drawable for DrawingCell<'_> {
                 ctx.move_to(x_pos, y_pos);
                 ctx.show_text(&digit);
             }
-            CellValue::Unknown(options) => {
-                assert!(
-                    options.len() <= 9,
-                    "Too many options marks, something went super wrong"
-                );
+            CellValue::Unknown(_) => {
+                let notes = self.sudoku.notes(self.position);
 
-                ctx.set_font_size(0.3);
                 ctx.set_color(rgb(0x808080));
 
-                for (i, pencil_mark) in options.iter().copied().enumerate() {
-                    let y_offset = (i / 3) as f64 * (1.0 / 3.0);
-                    let x_offset = (i % 3) as f64 * (1.0 / 3.0);
+                // Corner marks fill the corners first, then the edges
+                ctx.set_font_size(0.3);
+                for (i, pencil_mark) in notes.corner.iter().enumerate() {
+                    let (column, row) = CORNER_SLOTS[i];
+                    let x_offset = column as f64 * (1.0 / 3.0);
+                    let y_offset = row as f64 * (1.0 / 3.0);
 
                     let digit = u8::from(pencil_mark).to_string();
                     let text_extents = ctx.text_extents(&digit);
@@ -162,7 +233,36 @@ impl Drawable for DrawingCell<'_> {
                     ctx.move_to(x_pos, y_pos);
                     ctx.show_text(&digit);
                 }
+
+                // Centre marks shrink to fit on one line
+                if !notes.centre.is_empty() {
+                    let digits: String = notes
+                        .centre
+                        .iter()
+                        .map(|digit| (b'0' + u8::from(digit)) as char)
+                        .collect();
+
+                    ctx.set_font_size(0.3);
+                    let width = ctx.text_extents(&digits).width;
+                    if width > 0.6 {
+                        ctx.set_font_size(0.3 * 0.6 / width);
+                    }
+
+                    let text_extents = ctx.text_extents(&digits);
+                    let x_pos = 0.5 - text_extents.width / 2.0 - text_extents.x_bearing;
+                    let y_pos = 0.5 - text_extents.height / 2.0 - text_extents.y_bearing;
+
+                    ctx.move_to(x_pos, y_pos);
+                    ctx.show_text(&digits);
+                }
             }
         }
+
+        if self.is_selected {
+            ctx.set_color(rgba(0xffb300d0));
+            ctx.set_line_width(0.08);
+            ctx.rectangle(0.04, 0.04, 0.92, 0.92);
+            ctx.stroke();
+        }
     }
 }
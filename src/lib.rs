@@ -0,0 +1,3 @@
+mod sudoku;
+
+pub use crate::sudoku::*;
@@ -1,17 +1,179 @@
-use std::{collections::{BTreeSet, HashSet}, env::args, fs, sync::{Arc, RwLock}, thread, time::Duration};
+use std::{
+    cell::{Cell, RefCell},
+    convert::TryFrom,
+    env::args,
+    rc::Rc,
+    sync::{Arc, RwLock},
+    thread,
+    time::Duration,
+};
 
 use cairo::Rectangle;
-use draw::Drawable;
-use gio::prelude::*;
+use cli::Status;
+use draw::{Board, Drawable};
+use gdk::{keys::constants as keys, EventKey, EventMask, Keymap, ModifierType};
+use gio::{prelude::*, SimpleAction};
 use glib::clone;
-use gtk::{prelude::*, Align, AspectFrame, Box, Button, ButtonBox, DrawingArea, Orientation};
-use sudoku::{CellValue, Sudoku};
-
+use gtk::{
+    prelude::*, Align, AspectFrame, Box, Button, ButtonBox, DrawingArea, Orientation, ToggleButton,
+};
+use selection::Selection;
+use sudoku::{
+    solver::{Solver, Step},
+    CellOrigin, Digit, History, NoteMode, Notes, Position, Rules,
+};
+
+mod cli;
 mod color;
 mod draw;
-mod sudoku;
+mod selection;
+
+const DEFAULT_PUZZLE: &str = "sudoku.txt";
+
+/// Sent from the solver thread to the UI
+enum Update {
+    Step,
+    Finished,
+}
+
+/// The window actions which change the history of the board
+#[derive(Clone)]
+struct HistoryActions {
+    undo: SimpleAction,
+    redo: SimpleAction,
+    revert: SimpleAction,
+}
+
+impl HistoryActions {
+    fn new(window: &gtk::ApplicationWindow) -> Self {
+        let actions = HistoryActions {
+            undo: SimpleAction::new("undo", None),
+            redo: SimpleAction::new("redo", None),
+            revert: SimpleAction::new("revert", None),
+        };
+
+        window.add_action(&actions.undo);
+        window.add_action(&actions.redo);
+        window.add_action(&actions.revert);
+
+        actions
+    }
+
+    /// Enables the actions which have something to do, none of them while the
+    /// solver is running
+    fn update(&self, history: &History, solving: bool) {
+        self.undo.set_enabled(!solving && history.can_undo());
+        self.redo.set_enabled(!solving && history.can_redo());
+        self.revert.set_enabled(!solving && history.can_undo());
+    }
+}
+
+/// The cell under a point of the drawing area, in pixels
+fn cell_at(area: &DrawingArea, (x, y): (f64, f64)) -> Option<Position> {
+    draw::position_at(
+        x / area.get_allocated_width() as f64,
+        y / area.get_allocated_height() as f64,
+    )
+}
+
+/// The digit on the key, ignoring Shift and Ctrl so that they can pick the
+/// kind of note instead
+fn digit_of(event: &EventKey) -> Option<Digit> {
+    let mut state = event.get_state();
+    state.remove(ModifierType::SHIFT_MASK | ModifierType::CONTROL_MASK);
+
+    let keyval = Keymap::get_default()
+        .and_then(|keymap| {
+            keymap.translate_keyboard_state(
+                event.get_hardware_keycode() as u32,
+                state,
+                event.get_group() as i32,
+            )
+        })
+        .map_or(*event.get_keyval(), |(keyval, ..)| keyval);
+
+    let digit = gdk::keyval_to_unicode(keyval)?.to_digit(10)?;
+
+    Digit::try_from(digit as u8).ok()
+}
 
-fn build_ui(application: &gtk::Application, sudoku: Arc<RwLock<Sudoku>>) {
+/// Arrows move the cursor, digits are placed or noted and Delete clears the
+/// selected cells. Shift writes corner marks and Ctrl centre marks, and
+/// either extends the selection with the arrows. Returns whether the key was
+/// used.
+fn handle_key(history: &mut History, selection: &mut Selection, event: &EventKey) -> bool {
+    let state = event.get_state();
+    let shift = state.contains(ModifierType::SHIFT_MASK);
+    let control = state.contains(ModifierType::CONTROL_MASK);
+    let key = event.get_keyval();
+
+    let direction = match key {
+        keys::Up | keys::KP_Up => Some((-1, 0)),
+        keys::Down | keys::KP_Down => Some((1, 0)),
+        keys::Left | keys::KP_Left => Some((0, -1)),
+        keys::Right | keys::KP_Right => Some((0, 1)),
+        _ => None,
+    };
+
+    if let Some((rows, columns)) = direction {
+        selection.move_cursor(rows, columns, shift || control);
+
+        return true;
+    }
+
+    // Every key press is undone in one go, however many cells it changed
+    history.begin_group();
+
+    let handled = match key {
+        keys::Escape => {
+            selection.clear();
+            true
+        }
+        keys::Delete | keys::KP_Delete | keys::BackSpace => {
+            for position in selection.cells() {
+                if history.sudoku().get_raw(position).is_none() {
+                    history.edit(|sudoku| sudoku.set_notes(position, Notes::default()));
+                } else if let Err(error) = history.try_clear(position) {
+                    eprintln!("{}", error);
+                }
+            }
+            true
+        }
+        _ => match digit_of(event) {
+            Some(digit) => {
+                let mode = match (shift, control) {
+                    (true, _) => Some(NoteMode::Corner),
+                    (false, true) => Some(NoteMode::Centre),
+                    (false, false) => None,
+                };
+
+                for position in selection.cells() {
+                    match mode {
+                        Some(mode) if history.sudoku().get_raw(position).is_none() => {
+                            history.toggle_note(position, digit, mode);
+                        }
+                        Some(_) => {}
+                        None => {
+                            if let Err(error) =
+                                history.try_set(position, digit, CellOrigin::Player, Rules::Lenient)
+                            {
+                                eprintln!("{}", error);
+                            }
+                        }
+                    }
+                }
+                true
+            }
+            None => false,
+        },
+    };
+
+    history.end_group();
+
+    handled
+}
+
+fn build_ui(application: &gtk::Application, history: Arc<RwLock<History>>) {
     let window = gtk::ApplicationWindow::new(application);
     let box_container = Box::new(Orientation::Vertical, 5);
 
@@ -34,13 +196,84 @@ fn build_ui(application: &gtk::Application, sudoku: Arc<RwLock<Sudoku>>) {
     start_button.set_label("Solve");
     buttons.add(&start_button);
 
-    drawing_area.connect_draw(clone!(@strong sudoku => move |a, cr| {
+    let actions = HistoryActions::new(&window);
+    actions.update(&history.read().unwrap(), false);
+
+    let selection = Rc::new(RefCell::new(Selection::default()));
+    let solving = Rc::new(Cell::new(false));
+
+    for (label, action) in [
+        ("Undo", "win.undo"),
+        ("Redo", "win.redo"),
+        ("Revert to correct", "win.revert"),
+    ]
+    .iter()
+    {
+        let button = Button::new();
+        button.set_label(label);
+        button.set_action_name(Some(action));
+        buttons.add(&button);
+    }
+
+    application.set_accels_for_action("win.undo", &["<Primary>z"]);
+    application.set_accels_for_action("win.redo", &["<Primary><Shift>z"]);
+
+    let auto_notes_button = ToggleButton::new();
+    auto_notes_button.set_label("Auto notes");
+    auto_notes_button.set_active(history.read().unwrap().sudoku().auto_notes());
+    buttons.add(&auto_notes_button);
+
+    auto_notes_button.connect_toggled(
+        clone!(@strong history, @strong actions, @strong drawing_area => move |button| {
+            let mut history = history.write().unwrap();
+            history.set_auto_notes(button.get_active());
+
+            actions.update(&history, false);
+            drawing_area.queue_draw();
+        }),
+    );
+
+    actions.undo.connect_activate(
+        clone!(@strong history, @strong actions, @strong drawing_area => move |_, _| {
+            let mut history = history.write().unwrap();
+            history.undo();
+
+            actions.update(&history, false);
+            drawing_area.queue_draw();
+        }),
+    );
+    actions.redo.connect_activate(
+        clone!(@strong history, @strong actions, @strong drawing_area => move |_, _| {
+            let mut history = history.write().unwrap();
+            history.redo();
+
+            actions.update(&history, false);
+            drawing_area.queue_draw();
+        }),
+    );
+    actions.revert.connect_activate(
+        clone!(@strong history, @strong actions, @strong drawing_area => move |_, _| {
+            let mut history = history.write().unwrap();
+            history.revert_to_last_correct();
+
+            actions.update(&history, false);
+            drawing_area.queue_draw();
+        }),
+    );
+
+    drawing_area.connect_draw(clone!(@strong history, @strong selection => move |a, cr| {
         cr.scale(
             a.get_allocated_width() as f64,
             a.get_allocated_height() as f64,
         );
 
-        sudoku.read().unwrap().draw(
+        let history = history.read().unwrap();
+        let board = Board {
+            sudoku: history.sudoku(),
+            selection: &selection.borrow(),
+        };
+
+        board.draw(
             cr,
             Rectangle {
                 x: 0.0,
@@ -54,72 +287,96 @@ fn build_ui(application: &gtk::Application, sudoku: Arc<RwLock<Sudoku>>) {
     }));
     drawing_area.set_size_request(500, 500);
 
+    // Clicking selects a cell, or adds it to the selection with Shift or Ctrl,
+    // and dragging adds every cell passed over
+    drawing_area.add_events(EventMask::BUTTON_PRESS_MASK | EventMask::BUTTON1_MOTION_MASK);
+    drawing_area.connect_button_press_event(clone!(@strong selection => move |area, event| {
+        if let Some(position) = cell_at(area, event.get_position()) {
+            let extend = event
+                .get_state()
+                .intersects(ModifierType::SHIFT_MASK | ModifierType::CONTROL_MASK);
+
+            selection.borrow_mut().select(position, extend);
+            area.queue_draw();
+        }
+
+        Inhibit(true)
+    }));
+    drawing_area.connect_motion_notify_event(clone!(@strong selection => move |area, event| {
+        if let Some(position) = cell_at(area, event.get_position()) {
+            if !selection.borrow().contains(position) {
+                selection.borrow_mut().select(position, true);
+                area.queue_draw();
+            }
+        }
+
+        Inhibit(true)
+    }));
+
+    window.connect_key_press_event(
+        clone!(@strong history, @strong selection, @strong solving, @strong actions, @strong drawing_area => move |_, event| {
+            // The solver expects to be the only one changing the board
+            if solving.get() {
+                return Inhibit(false);
+            }
+
+            let mut history = history.write().unwrap();
+
+            if handle_key(&mut history, &mut selection.borrow_mut(), event) {
+                actions.update(&history, false);
+                drawing_area.queue_draw();
+
+                Inhibit(true)
+            } else {
+                Inhibit(false)
+            }
+        }),
+    );
+
     let (tx, rx) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
 
     start_button.connect_button_press_event(
-        clone!(@strong sudoku => move |_, _| {
-            thread::spawn(clone!(@strong sudoku, @strong tx => move || {
-                let mut moves = Vec::new();
+        clone!(@strong history, @strong actions, @strong solving, @strong auto_notes_button => move |button, _| {
+            solving.set(true);
+            button.set_sensitive(false);
+            // The board is left to the solver until it is done
+            auto_notes_button.set_sensitive(false);
+            actions.update(&history.read().unwrap(), true);
+
+            thread::spawn(clone!(@strong history, @strong tx => move || {
+                let mut solver = Solver::new();
+
+                // The whole solve is undone in one go
+                history.write().unwrap().begin_group();
 
-                'outer: loop {
+                loop {
                     thread::sleep(Duration::from_millis(50));
 
-                    let mut sudoku_lock = sudoku.write().unwrap();
-
-                    let smallest = sudoku_lock.all().filter_map(|(pos, cell)| if let CellValue::Unknown(options) = cell {
-                        Some((pos, options))
-                    } else {
-                        None
-                    }).min_by_key(|(_, marks)| marks.len()).map(|(pos, marks)| (pos, marks.iter().next().copied()));
-
-                    if let Some((pos, mark)) = smallest {
-                        if let Some(digit) = mark {
-                            moves.push((pos, {
-                                let mut set = BTreeSet::new();
-                                set.insert(digit);
-                                set
-                            }));
-                            sudoku_lock.set(pos, Some(digit));
-                        } else {
-                            eprintln!("Invalid board, ran into 0 pencil marks at {:?}", pos);
-                            eprintln!("Backtracking from {:?}", moves.last());
-
-                            drop(sudoku_lock); // Clear other lock
-                            loop {
-                                thread::sleep(Duration::from_millis(50));
-                                let mut sudoku_lock = sudoku.write().unwrap();
-
-                                if let Some((back_move, mut back_digits)) = moves.pop() {
-                                    let possibilities = sudoku_lock.possibilities(back_move);
-
-                                    if let Some(other_possibility) = possibilities.difference(&back_digits).next().map(|x| *x) {
-                                        sudoku_lock.set(back_move, Some(other_possibility));
-
-                                        back_digits.insert(other_possibility);
-                                        moves.push((back_move, back_digits));
-                                        break;
-                                    } else {
-                                        sudoku_lock.set(back_move, None);
-                                    }
-                                } else {
-                                    eprintln!("Backtracked to the start");
-                                    
-                                    break 'outer;
-                                }
-
-                                tx.send(()).expect("Could not poll refresh");
-                            }
+                    let step = history.write().unwrap().edit(|sudoku| solver.step(sudoku));
+
+                    match step {
+                        Step::Placed(..) | Step::Retried(..) => {}
+                        Step::Backtracked(pos) => {
+                            eprintln!("Backtracking from {}", pos);
                         }
-                    } else {
-                        eprintln!("Solved?");
+                        Step::Solved => {
+                            eprintln!("Solved");
 
-                        break;
+                            break;
+                        }
+                        Step::Unsolvable => {
+                            eprintln!("Backtracked to the start");
+
+                            break;
+                        }
                     }
 
-                    tx.send(()).expect("Could not poll refresh");
+                    tx.send(Update::Step).expect("Could not poll refresh");
                 }
-                
-                tx.send(()).expect("Could not poll refresh");
+
+                history.write().unwrap().end_group();
+
+                tx.send(Update::Finished).expect("Could not poll refresh");
             }));
 
 
@@ -129,22 +386,20 @@ fn build_ui(application: &gtk::Application, sudoku: Arc<RwLock<Sudoku>>) {
 
     rx.attach(
         None,
-        clone!(@strong drawing_area => move |_: ()| {
+        clone!(@strong history, @strong actions, @strong solving, @strong start_button, @strong auto_notes_button, @strong drawing_area => move |update| {
+            if let Update::Finished = update {
+                solving.set(false);
+                start_button.set_sensitive(true);
+                auto_notes_button.set_sensitive(true);
+                actions.update(&history.read().unwrap(), false);
+            }
+
             drawing_area.queue_draw();
 
             glib::Continue(true)
         }),
     );
 
-    // window.connect_button_press_event();
-    // window.connect_motion_notify_event(clone!(@strong sudoku, @strong drawing_area => move |_, motion| {
-    //     sudoku.write().unwrap()[4][4].digit = Some((motion.get_position().0 % 9.0 + 1.0) as u8);
-    //     drawing_area.queue_draw();
-
-    //     Inhibit(false)
-    // }));
-    // window.add_events(EventMask::POINTER_MOTION_MASK);
-
     window.set_default_size(500, 500);
 
     window.add(&box_container);
@@ -157,18 +412,50 @@ fn build_ui(application: &gtk::Application, sudoku: Arc<RwLock<Sudoku>>) {
     }));
 }
 
-fn main() {
+fn gui(path: &str) -> Status {
+    let history = match cli::load(path) {
+        Ok(sudoku) => Arc::new(RwLock::new(History::new(sudoku))),
+        Err(status) => return status,
+    };
+
     let application = gtk::Application::new(Some("com.dusterthefirst.sudoku"), Default::default())
         .expect("Initialization failed...");
 
-    let sudoku = fs::read_to_string(args().nth(1).unwrap_or_else(|| "sudoku.txt".into())).unwrap();
-    let sudoku = Arc::new(RwLock::new(
-        sudoku.parse().unwrap_or_else(|e| panic!("{}", e)),
-    ));
-
     application.connect_activate(move |app| {
-        build_ui(app, Arc::clone(&sudoku));
+        build_ui(app, Arc::clone(&history));
     });
 
     application.run(&[]);
+
+    Status::Success
+}
+
+fn main() {
+    let args: Vec<String> = args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let status = match args.as_slice() {
+        ["solve", path] => cli::solve(path),
+        ["check", path] => cli::check(path),
+        ["print", path] => cli::print(path),
+        ["explain", path] => cli::explain(path),
+        ["grade", path] => cli::grade(path),
+        ["generate", options @ ..] => cli::generate(options),
+        ["batch", task, path] => cli::batch(task, path),
+        ["help"] | ["-h"] | ["--help"] => {
+            println!("{}", cli::USAGE);
+
+            Status::Success
+        }
+        [] | ["gui"] => gui(DEFAULT_PUZZLE),
+        ["gui", path] => gui(path),
+        [path] if !cli::COMMANDS.contains(path) => gui(path),
+        _ => {
+            eprintln!("{}", cli::USAGE);
+
+            Status::Usage
+        }
+    };
+
+    status.exit()
 }
@@ -0,0 +1,54 @@
+use std::collections::BTreeSet;
+
+use sudoku::Position;
+
+/// The cells the player is working on, along with the one the arrow keys move
+/// from
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    cells: BTreeSet<Position>,
+    cursor: Option<Position>,
+}
+
+impl Selection {
+    pub fn contains(&self, position: Position) -> bool {
+        self.cells.contains(&position)
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = Position> + '_ {
+        self.cells.iter().copied()
+    }
+
+    /// Moves the cursor to `position`, selecting only that cell unless
+    /// `extend` is set
+    pub fn select(&mut self, position: Position, extend: bool) {
+        if !extend {
+            self.cells.clear();
+        }
+
+        self.cells.insert(position);
+        self.cursor = Some(position);
+    }
+
+    /// Moves the cursor by a number of rows and columns, wrapping around the
+    /// edges of the board. Starts from the top left cell if nothing is
+    /// selected yet.
+    pub fn move_cursor(&mut self, rows: isize, columns: isize, extend: bool) {
+        let position = match self.cursor {
+            Some(cursor) => {
+                let row = (cursor.row() as isize + rows).rem_euclid(9) as usize;
+                let column = (cursor.column() as isize + columns).rem_euclid(9) as usize;
+
+                Position::new(row, column).expect("wrapped position is on the board")
+            }
+            None => Position::new(0, 0).expect("r1c1 is on the board"),
+        };
+
+        self.select(position, extend);
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.cursor = None;
+    }
+}
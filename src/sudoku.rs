@@ -1,13 +1,42 @@
-use petgraph::{graph::NodeIndex, visit::EdgeRef, Graph, Undirected};
+use petgraph::{graph::NodeIndex, Graph, Undirected};
 use std::{
-    collections::{BTreeSet, HashSet},
     convert::{TryFrom, TryInto},
-    fmt::Debug,
+    fmt::{self, Debug, Display},
+    mem,
     str::FromStr,
 };
 use thiserror::Error;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub mod batch;
+pub mod candidates;
+pub mod conflicts;
+pub mod dlx;
+pub mod generator;
+pub mod grade;
+pub mod history;
+pub mod house;
+pub mod logic;
+pub mod minimal;
+pub mod moves;
+pub mod notes;
+pub mod position;
+pub mod relations;
+pub mod rules;
+pub mod solver;
+
+pub use self::{
+    candidates::Candidates,
+    conflicts::Conflict,
+    history::History,
+    house::{peers, House},
+    moves::Move,
+    notes::{NoteMode, Notes},
+    position::Position,
+    relations::Relations,
+    rules::{MoveError, Rules},
+};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub enum Relation {
     Block,
     Row,
@@ -21,76 +50,163 @@ pub enum SolveStatus {
     Invalid,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Cell {
     pub value: Digit,
-    pub is_given: bool,
+    pub origin: CellOrigin,
+    /// The move which placed the digit, 0 if it was not placed by a move,
+    /// like givens, imported digits and those of the solver
+    pub placed_at: u32,
+}
+
+impl Cell {
+    pub fn is_given(&self) -> bool {
+        self.origin == CellOrigin::Given
+    }
+}
+
+/// Who placed the digit of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CellOrigin {
+    /// Part of the puzzle
+    Given,
+    Player,
+    Solver,
+    /// Revealed to the player on request
+    Hint,
+    /// Loaded along with the puzzle, but not one of its givens
+    Imported,
+}
+
+impl CellOrigin {
+    /// The character written before a digit in a progress string, givens
+    /// having none
+    pub fn marker(self) -> Option<char> {
+        match self {
+            CellOrigin::Given => None,
+            CellOrigin::Player => Some('+'),
+            CellOrigin::Solver => Some('*'),
+            CellOrigin::Hint => Some('?'),
+            CellOrigin::Imported => Some('~'),
+        }
+    }
+
+    fn from_marker(marker: char) -> Option<Self> {
+        [
+            CellOrigin::Player,
+            CellOrigin::Solver,
+            CellOrigin::Hint,
+            CellOrigin::Imported,
+        ]
+        .iter()
+        .copied()
+        .find(|origin| origin.marker() == Some(marker))
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum CellValue<'a> {
     Known(&'a Cell),
-    Unknown(BTreeSet<Digit>),
+    Unknown(Candidates),
 }
 
-#[derive(Debug)]
-pub struct Sudoku(Graph<Option<Cell>, Relation, Undirected>);
+/// A board of 81 cells, along with the digits placed in each house and the
+/// candidates of each cell, which are kept up to date by [`Sudoku::set`]
+#[derive(Debug, Clone)]
+pub struct Sudoku {
+    cells: [Option<Cell>; 81],
+    /// The digits in each house, in the order of [`House::all`]
+    houses: [Candidates; 27],
+    /// How often each digit is in each house, as a board being filled in can
+    /// have the same digit twice
+    counts: [[u8; 9]; 27],
+    /// The digits not placed in any of the cells seeing each cell
+    candidates: [Candidates; 81],
+    /// How many moves have been made since the board was loaded
+    moves: u32,
+    notes: [Notes; 81],
+    auto_notes: bool,
+}
 
 impl Sudoku {
     pub fn new() -> Self {
+        Sudoku {
+            cells: [None; 81],
+            houses: [Candidates::none(); 27],
+            counts: [[0; 9]; 27],
+            candidates: [Candidates::all(); 81],
+            moves: 0,
+            notes: [Notes::default(); 81],
+            auto_notes: false,
+        }
+    }
+
+    /// The board as a graph of cells in reading order, with an edge between
+    /// every two cells which see each other, carrying every house they share
+    pub fn graph(&self) -> Graph<Option<Cell>, Relations, Undirected> {
         let mut graph = Graph::new_undirected();
-        let mut all_cells = Vec::with_capacity(9 * 9);
 
-        for x in 0..9 {
-            for y in 0..9 {
-                all_cells.push(((x, y), x / 3 + (y / 3) * 3, graph.add_node(None)))
-            }
+        for position in Position::all() {
+            graph.add_node(self.cells[position.index()]);
         }
 
-        for ((x, y), block, cell) in all_cells.iter() {
-            for ((other_x, other_y), other_block, other_cell) in
-                all_cells.iter().filter(|(_, _, idx)| idx != cell)
-            {
-                if other_block == block {
-                    graph.update_edge(*cell, *other_cell, Relation::Block);
-                } else if other_x == x {
-                    graph.update_edge(*cell, *other_cell, Relation::Column);
-                } else if other_y == y {
-                    graph.update_edge(*cell, *other_cell, Relation::Row);
+        for position in Position::all() {
+            for other in Position::all().filter(|&other| other > position) {
+                let relations = Relations::between(position, other);
+
+                if !relations.is_empty() {
+                    graph.add_edge(
+                        NodeIndex::new(position.index()),
+                        NodeIndex::new(other.index()),
+                        relations,
+                    );
                 }
             }
         }
 
-        Sudoku(graph)
+        graph
     }
 
-    // pub fn solved(&self) -> bool {
-    //     // TODO: Check rows and columns
-    //     // self.iter().all(|x| x.valid())
-    //     false
-    // }
-
-    pub fn block_status(&self, (block_x, block_y): (usize, usize)) -> SolveStatus {
-        let mut digits = HashSet::new();
+    /// Whether every cell has a digit and no house has a digit twice
+    pub fn status(&self) -> SolveStatus {
+        let statuses: Vec<_> = House::all().map(|house| self.house_status(house)).collect();
 
-        for cell in self.neighbors((block_x * 3, block_y * 3), Relation::Block) {
-            match cell {
-                Some(Cell { value, .. }) => {
-                    if !digits.insert(value) {
-                        return SolveStatus::Invalid;
-                    }
-                }
-                None => {
-                    return SolveStatus::Unsolved;
-                }
-            }
+        if statuses.contains(&SolveStatus::Invalid) {
+            SolveStatus::Invalid
+        } else if statuses.contains(&SolveStatus::Unsolved) {
+            SolveStatus::Unsolved
+        } else {
+            SolveStatus::Solved
         }
+    }
+
+    pub fn row_status(&self, y: usize) -> SolveStatus {
+        self.house_status(House::Row(y))
+    }
 
-        SolveStatus::Solved
+    pub fn column_status(&self, x: usize) -> SolveStatus {
+        self.house_status(House::Column(x))
     }
 
-    pub fn cell_status(&self, (x, y): (usize, usize)) -> SolveStatus {
-        let cell_digit = match self.get_raw((x, y)) {
+    /// The status of a block, numbered left to right, top to bottom
+    pub fn block_status(&self, block: usize) -> SolveStatus {
+        self.house_status(House::Block(block))
+    }
+
+    /// A house is invalid as soon as it has a digit twice, even if it still has
+    /// empty cells
+    pub fn house_status(&self, house: House) -> SolveStatus {
+        if self.counts[house.id()].iter().any(|&count| count > 1) {
+            SolveStatus::Invalid
+        } else if self.houses[house.id()] == Candidates::all() {
+            SolveStatus::Solved
+        } else {
+            SolveStatus::Unsolved
+        }
+    }
+
+    pub fn cell_status(&self, position: Position) -> SolveStatus {
+        let cell_digit = match self.get_raw(position) {
             Some(Cell { value, .. }) => value,
             None => {
                 return SolveStatus::Unsolved;
@@ -98,7 +214,7 @@ impl Sudoku {
         };
 
         let mut has_empty = false;
-        for cell in self.all_neighbors((x, y)) {
+        for cell in self.all_neighbors(position) {
             match cell {
                 Some(Cell { value, .. }) => {
                     if &cell_digit == value {
@@ -118,36 +234,37 @@ impl Sudoku {
         }
     }
 
-    // TODO: Row, column and cell display
+    /// The other 8 cells of the row, column or block of `position`
     pub fn neighbors(
         &self,
-        (x, y): (usize, usize),
+        position: Position,
         relation: Relation,
     ) -> impl Iterator<Item = &Option<Cell>> {
-        self.0
-            .edges(Self::index_of((x, y)))
-            .filter(move |x| x.weight() == &relation)
-            .map(move |x| &self.0[x.target()])
-    }
-
-    pub fn all_neighbors(&self, (x, y): (usize, usize)) -> impl Iterator<Item = &Option<Cell>> {
-        self.0
-            .edges(Self::index_of((x, y)))
-            .map(move |x| &self.0[x.target()])
+        let house = House::new(
+            relation,
+            match relation {
+                Relation::Row => position.row(),
+                Relation::Column => position.column(),
+                Relation::Block => position.block(),
+            },
+        );
+
+        house
+            .cells()
+            .filter(move |&other| other != position)
+            .map(move |other| &self.cells[other.index()])
     }
 
-    fn index_of((x, y): (usize, usize)) -> NodeIndex {
-        NodeIndex::new(x * 9 + y)
+    pub fn all_neighbors(&self, position: Position) -> impl Iterator<Item = &Option<Cell>> {
+        peers(position).map(move |other| &self.cells[other.index()])
     }
 
-    pub fn all_raw(&self) -> impl Iterator<Item = ((usize, usize), &Option<Cell>)> {
-        (0..9)
-            .map(|x| (0..9).map(move |y| (x, y)))
-            .flatten()
-            .map(move |coords| (coords, &self.0[Self::index_of(coords)]))
+    /// Every cell in reading order
+    pub fn all_raw(&self) -> impl Iterator<Item = (Position, &Option<Cell>)> {
+        Position::all().map(move |position| (position, &self.cells[position.index()]))
     }
 
-    pub fn all(&self) -> impl Iterator<Item = ((usize, usize), CellValue)> {
+    pub fn all(&self) -> impl Iterator<Item = (Position, CellValue<'_>)> {
         self.all_raw().map(move |(pos, c)| {
             (
                 pos,
@@ -159,98 +276,457 @@ impl Sudoku {
         })
     }
 
-    pub fn get_raw(&self, (x, y): (usize, usize)) -> Option<Cell> {
-        self.0[Self::index_of((x, y))]
+    pub fn get_raw(&self, position: Position) -> Option<Cell> {
+        self.cells[position.index()]
     }
 
-    pub(self) fn set_given(&mut self, (x, y): (usize, usize), value: Digit) {
-        self.0[Self::index_of((x, y))] = Some(Cell {
-            is_given: true,
-            value,
-        });
+    pub(self) fn set_given(&mut self, position: Position, value: Digit) {
+        self.set_as(position, Some(value), CellOrigin::Given);
     }
 
-    pub fn set(&mut self, (x, y): (usize, usize), value: Option<Digit>) {
-        self.0[Self::index_of((x, y))] = value.map(|value| Cell {
-            is_given: false,
-            value,
-        });
+    /// Changes the cell without any checks, even if it is a given, as the
+    /// solver. This is not a move, see [`Sudoku::try_set`] and
+    /// [`Sudoku::try_clear`] for moves made by a player
+    pub fn set(&mut self, position: Position, value: Option<Digit>) {
+        self.set_as(position, value, CellOrigin::Solver);
     }
 
-    pub fn get(&self, (x, y): (usize, usize)) -> CellValue {
-        let idx = Self::index_of((x, y));
-        match &self.0[idx] {
-            Some(c) => CellValue::Known(c),
-            None => CellValue::Unknown(self.possibilities((x, y))),
-        }
+    /// Like [`Sudoku::set`], recording `origin` on the digit
+    pub fn set_as(&mut self, position: Position, value: Option<Digit>, origin: CellOrigin) {
+        self.replace(
+            position,
+            value.map(|value| Cell {
+                value,
+                origin,
+                placed_at: 0,
+            }),
+        );
+    }
+
+    /// Changes the cell as the next move, numbering the digit placed
+    pub(crate) fn play(&mut self, position: Position, value: Option<Digit>, origin: CellOrigin) {
+        self.moves = self.moves.wrapping_add(1);
+
+        self.replace(
+            position,
+            value.map(|value| Cell {
+                value,
+                origin,
+                placed_at: self.moves,
+            }),
+        );
+    }
+
+    /// How many moves have been made since the board was loaded
+    pub fn move_count(&self) -> u32 {
+        self.moves
     }
 
-    pub fn possibilities(&self, (x, y): (usize, usize)) -> BTreeSet<Digit> {
-        let mut digits: BTreeSet<_> = Digit::iterator().collect();
+    /// How many digits on the board were placed by `origin`, such as the
+    /// number of hints used
+    pub fn count_origin(&self, origin: CellOrigin) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|cell| cell.origin == origin)
+            .count()
+    }
+
+    /// Stores `cell` at `position`, updating the digits of its houses and the
+    /// candidates of every cell in them
+    fn replace(&mut self, position: Position, cell: Option<Cell>) {
+        let houses = House::of(position);
+        let old = mem::replace(&mut self.cells[position.index()], cell);
+
+        for house in houses.iter().map(|house| house.id()) {
+            if let Some(old) = old {
+                let count = &mut self.counts[house][u8::from(old.value) as usize - 1];
+                *count -= 1;
+
+                if *count == 0 {
+                    self.houses[house].remove(old.value);
+                }
+            }
 
-        for neighbor in self.all_neighbors((x, y)) {
-            if let Some(digit) = neighbor {
-                digits.remove(&digit.value);
+            if let Some(cell) = cell {
+                self.counts[house][u8::from(cell.value) as usize - 1] += 1;
+                self.houses[house].insert(cell.value);
             }
         }
 
-        digits
+        for other in houses.iter().flat_map(|house| house.cells()) {
+            self.candidates[other.index()] = self.candidates_of(other);
+        }
+
+        // The solver's guesses may be taken back, so they leave the notes alone
+        match cell {
+            Some(cell) if self.auto_notes && cell.origin != CellOrigin::Solver => {
+                self.remove_from_peer_notes(position, cell.value);
+            }
+            _ => {}
+        }
+    }
+
+    fn candidates_of(&self, position: Position) -> Candidates {
+        let houses = House::of(position).map(House::id);
+        let [row, column, block] = houses;
+        let seen = self.houses[row] | self.houses[column] | self.houses[block];
+
+        match self.cells[position.index()] {
+            // The digits of the houses include the cell's own, which only
+            // counts if it is there twice
+            Some(cell)
+                if houses
+                    .iter()
+                    .all(|&house| self.counts[house][u8::from(cell.value) as usize - 1] == 1) =>
+            {
+                !(seen - Candidates::single(cell.value))
+            }
+            _ => !seen,
+        }
+    }
+
+    pub fn get(&self, position: Position) -> CellValue<'_> {
+        match &self.cells[position.index()] {
+            Some(c) => CellValue::Known(c),
+            None => CellValue::Unknown(self.possibilities(position)),
+        }
+    }
+
+    /// The digits none of the cells seeing `position` have
+    pub fn possibilities(&self, position: Position) -> Candidates {
+        self.candidates[position.index()]
     }
 }
 
-#[derive(Error, Debug, Clone, Copy)]
+impl Default for Sudoku {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Error, Debug, Clone)]
 pub enum SudokuParseError {
-    #[error("the sudoku board was of invalid size. expected length of 81, found {0}")]
+    #[error("the sudoku board was too short. expected 81 cells, found {0}")]
     TooShort(usize),
-    #[error("encountered an invalid character: {0}")]
-    InvalidChar(char),
-    #[error("the board provided is invalid at ({0}, {1})")]
-    InvalidSudoku(usize, usize),
+    #[error("the sudoku board was too long. expected 81 cells")]
+    TooLong(Location),
+    #[error("encountered an invalid character: {0:?}")]
+    InvalidChar(char, Location),
+    #[error("the board provided has {} conflicts", .0.len())]
+    InvalidSudoku(Vec<Conflict>),
+}
+
+impl SudokuParseError {
+    /// Where in the text the error is, if it is about a single character
+    pub fn location(&self) -> Option<Location> {
+        match *self {
+            SudokuParseError::TooLong(location) | SudokuParseError::InvalidChar(_, location) => {
+                Some(location)
+            }
+            SudokuParseError::TooShort(_) | SudokuParseError::InvalidSudoku(_) => None,
+        }
+    }
+}
+
+/// A position in the text of a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// The line, starting at 1
+    pub line: usize,
+    /// The character within the line, starting at 1
+    pub column: usize,
+    /// The byte offset from the start of the text
+    pub offset: usize,
+}
+
+impl Location {
+    /// The line of `source` this location is in, with a caret under it on the
+    /// line below
+    pub fn caret(&self, source: &str) -> String {
+        let line = source.lines().nth(self.line - 1).unwrap_or_default();
+
+        format!(
+            "{}\n{}^",
+            line.trim_end_matches('\r'),
+            " ".repeat(self.column - 1)
+        )
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
 }
 
 impl FromStr for Sudoku {
     type Err = SudokuParseError;
 
+    /// Reads a board in any of the common formats: 9 lines or a single line
+    /// of 81 cells, with `-`, `.` or `0` for empty cells. Whitespace, `|`
+    /// and `+---+` separator lines are skipped, as are lines starting with `#`
+    /// or `//`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.replace("\n", "");
+        let (cells, _) = read_cells(s, false)?;
+        let mut sudoku = Sudoku::new();
 
-        if s.len() != 81 {
-            return Err(SudokuParseError::TooShort(s.len()));
+        for (pos, cell) in Position::all().zip(cells.iter()) {
+            if let Some(cell) = cell {
+                sudoku.set_given(pos, cell.value);
+            }
         }
 
-        let mut sudoku = Sudoku::new();
+        sudoku.validate()?;
 
-        for block in 0..9 {
-            for cell in 0..9 {
-                let x = (block % 3) * 3 + cell % 3;
-                let y = (block / 3) * 3 + cell / 3;
-
-                let char = s.chars().nth(x + y * 9).unwrap();
-
-                if char != '-' {
-                    sudoku.set_given(
-                        (x, y),
-                        (char
-                            .to_digit(10)
-                            .ok_or(SudokuParseError::InvalidChar(char))?
-                            as u8)
-                            .try_into()
-                            .map_err(|_| SudokuParseError::InvalidChar(char))?,
-                    );
+        Ok(sudoku)
+    }
+}
+
+/// A line which holds no cells
+fn is_comment(line: &str) -> bool {
+    let line = line.trim();
+
+    line.starts_with('#') || line.starts_with("//")
+}
+
+/// A line like `------+-------+------` or `---------------------`, which is
+/// not a row of 9 empty cells. Only dashes make up an empty board written on a
+/// single line. In a progress string `+` is a [`CellOrigin::marker`] instead.
+fn is_separator(line: &str, progress: bool) -> bool {
+    let punctuation = if progress { "-=|" } else { "-+=|" };
+    let dashes = line.chars().filter(|&char| char == '-').count();
+
+    line.chars()
+        .all(|char| punctuation.contains(char) || char.is_whitespace())
+        && (line.contains(['+', '=']) || (dashes > 9 && dashes != 81))
+}
+
+/// Reads the 81 cells of a board in reading order, along with the number of
+/// moves made. In a progress string the [`CellOrigin::marker`] before a digit
+/// says who placed it and `@` after it the move which did, and a last line of
+/// `@` and a number holds the move count. Otherwise digits are givens.
+fn read_cells(s: &str, progress: bool) -> Result<(Vec<Option<Cell>>, u32), SudokuParseError> {
+    let mut cells: Vec<Option<Cell>> = Vec::with_capacity(81);
+    let mut moves = None;
+    let mut offset = 0;
+
+    for (i, line) in s.split('\n').enumerate() {
+        let start = offset;
+        offset += line.len() + 1;
+
+        if is_comment(line) || is_separator(line, progress) {
+            continue;
+        }
+
+        let mut placed = None;
+        // Whether the last character was a digit which can have a move number
+        let mut numbered = false;
+        let mut chars = line.char_indices().enumerate().peekable();
+
+        while let Some((column, (byte, char))) = chars.next() {
+            let location = Location {
+                line: i + 1,
+                column: column + 1,
+                offset: start + byte,
+            };
+
+            let after_digit = mem::replace(&mut numbered, false);
+            let marker = CellOrigin::from_marker(char).filter(|_| progress);
+
+            let cell = match char {
+                _ if marker.is_some() => {
+                    placed = marker.map(|origin| (origin, char, location));
+                    continue;
                 }
+                '@' if progress && placed.is_none() => {
+                    let mut number = String::new();
+
+                    while let Some((_, (_, digit))) =
+                        chars.next_if(|(_, (_, char))| char.is_ascii_digit())
+                    {
+                        number.push(digit);
+                    }
+
+                    let number = number
+                        .parse()
+                        .map_err(|_| SudokuParseError::InvalidChar(char, location))?;
+
+                    let filled = cells.len();
+
+                    match cells.last_mut() {
+                        Some(Some(cell)) if after_digit => cell.placed_at = number,
+                        _ if filled == 81 && line[..byte].trim().is_empty() => {
+                            moves = Some(number);
+                        }
+                        _ => return Err(SudokuParseError::InvalidChar(char, location)),
+                    }
+
+                    continue;
+                }
+                '-' | '.' | '0' => None,
+                '1'..='9' => Some(Cell {
+                    value: parse_digit(char, location)?,
+                    origin: placed.map_or(CellOrigin::Given, |(origin, _, _)| origin),
+                    placed_at: 0,
+                }),
+                '|' | '+' => continue,
+                char if char.is_whitespace() => continue,
+                char => return Err(SudokuParseError::InvalidChar(char, location)),
+            };
+
+            if let (Some((_, marker, location)), None) = (placed, cell) {
+                return Err(SudokuParseError::InvalidChar(marker, location));
+            }
+            placed = None;
+            numbered = cell.map_or(false, |cell| !cell.is_given());
+
+            if cells.len() == 81 {
+                return Err(SudokuParseError::TooLong(location));
             }
+            cells.push(cell);
         }
 
-        for x in 0..9 {
-            for y in 0..9 {
-                if sudoku.cell_status((x, y)) == SolveStatus::Invalid {
-                    return Err(SudokuParseError::InvalidSudoku(x, y));
+        if let Some((_, marker, location)) = placed {
+            return Err(SudokuParseError::InvalidChar(marker, location));
+        }
+    }
+
+    if cells.len() != 81 {
+        return Err(SudokuParseError::TooShort(cells.len()));
+    }
+
+    // Later moves must not reuse the number of a digit on the board
+    let moves = cells
+        .iter()
+        .flatten()
+        .map(|cell| cell.placed_at)
+        .fold(moves.unwrap_or(0), u32::max);
+
+    Ok((cells, moves))
+}
+
+impl Display for Sudoku {
+    /// Writes the board in the 9 line format read by [`Sudoku::from_str`],
+    /// using `-` for empty cells
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (position, cell) in self.all_raw() {
+            if position.column() == 0 && position.row() != 0 {
+                writeln!(f)?;
+            }
+
+            match cell {
+                Some(Cell { value, .. }) => write!(f, "{}", u8::from(*value))?,
+                None => write!(f, "-")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Sudoku {
+    pub fn to_grid_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// Writes the board on a single line of 81 characters, the format used by
+    /// puzzle collections
+    pub fn to_line_string(&self) -> String {
+        self.to_string().replace('\n', "")
+    }
+
+    /// Writes the board like [`Sudoku::to_grid_string`], but prefixes every
+    /// digit that is not a given with the [`CellOrigin::marker`] of whoever
+    /// placed it and follows it with `@` and the number of the move which did,
+    /// like `+7@12`, and a space if the row goes on. A last line such as `@15`
+    /// holds the number of moves made,
+    /// so that a game in progress can be restored with
+    /// [`Sudoku::from_progress_str`]
+    pub fn to_progress_string(&self) -> String {
+        let mut progress = String::with_capacity(9 * 19);
+
+        let mut numbered = false;
+
+        for (position, cell) in self.all_raw() {
+            if position.column() == 0 && position.row() != 0 {
+                progress.push('\n');
+            } else if mem::replace(&mut numbered, false) {
+                // Keeps the move number apart from a given in the next cell
+                progress.push(' ');
+            }
+
+            match cell {
+                Some(Cell {
+                    value,
+                    origin,
+                    placed_at,
+                }) => {
+                    progress.extend(origin.marker());
+
+                    progress.push((b'0' + u8::from(*value)) as char);
+
+                    if *placed_at != 0 {
+                        progress.push_str(&format!("@{}", placed_at));
+                        numbered = true;
+                    }
                 }
+                None => progress.push('-'),
+            }
+        }
+
+        if self.moves != 0 {
+            progress.push_str(&format!("\n@{}", self.moves));
+        }
+
+        progress
+    }
+
+    /// Reads a board written by [`Sudoku::to_progress_string`]
+    ///
+    /// Only the givens have to agree with each other, other digits are
+    /// restored even if they are wrong, keeping the numbers of the moves which
+    /// placed them.
+    pub fn from_progress_str(s: &str) -> Result<Self, SudokuParseError> {
+        let (cells, moves) = read_cells(s, true)?;
+        let mut sudoku = Sudoku::new();
+
+        for (pos, cell) in Position::all().zip(cells.iter()) {
+            if let Some(cell) = cell.filter(Cell::is_given) {
+                sudoku.set_given(pos, cell.value);
             }
         }
 
+        sudoku.validate()?;
+
+        for (pos, cell) in Position::all().zip(cells.iter()) {
+            if let Some(cell) = cell.filter(|cell| !cell.is_given()) {
+                sudoku.replace(pos, Some(cell));
+            }
+        }
+
+        sudoku.moves = moves;
+
         Ok(sudoku)
     }
+
+    fn validate(&self) -> Result<(), SudokuParseError> {
+        let conflicts = self.conflicts();
+
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(SudokuParseError::InvalidSudoku(conflicts))
+        }
+    }
+}
+
+fn parse_digit(char: char, location: Location) -> Result<Digit, SudokuParseError> {
+    (char
+        .to_digit(10)
+        .ok_or(SudokuParseError::InvalidChar(char, location))? as u8)
+        .try_into()
+        .map_err(|_| SudokuParseError::InvalidChar(char, location))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -308,3 +784,240 @@ impl Digit {
         .copied()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "\
+---6-3---
+-3--1--5-
+--9---2--
+7--1-6--9
+-2-----8-
+1--4-9--3
+--8---1--
+-5--9--7-
+---7-4---";
+
+    fn puzzle() -> Sudoku {
+        PUZZLE.parse().unwrap()
+    }
+
+    fn at(row: usize, column: usize) -> Position {
+        Position::new(row, column).unwrap()
+    }
+
+    #[test]
+    fn grid_round_trip() {
+        assert_eq!(puzzle().to_grid_string(), PUZZLE);
+        assert_eq!(
+            puzzle()
+                .to_grid_string()
+                .parse::<Sudoku>()
+                .unwrap()
+                .to_string(),
+            PUZZLE
+        );
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let line = puzzle().to_line_string();
+
+        assert_eq!(line.len(), 81);
+        assert_eq!(line.parse::<Sudoku>().unwrap().to_string(), PUZZLE);
+        assert_eq!(
+            Sudoku::new()
+                .to_line_string()
+                .parse::<Sudoku>()
+                .unwrap()
+                .to_string(),
+            Sudoku::new().to_string()
+        );
+    }
+
+    #[test]
+    fn blanks_and_line_endings() {
+        let dots = PUZZLE.replace('-', ".").replace('\n', "\r\n");
+        let zeros = PUZZLE.replace('-', "0").replace('\n', " ");
+
+        assert_eq!(dots.parse::<Sudoku>().unwrap().to_string(), PUZZLE);
+        assert_eq!(zeros.parse::<Sudoku>().unwrap().to_string(), PUZZLE);
+    }
+
+    #[test]
+    fn separators_and_comments() {
+        let rows: Vec<_> = PUZZLE
+            .lines()
+            .map(|row| format!("{} | {} | {}", &row[..3], &row[3..6], &row[6..]))
+            .collect();
+
+        let separators = [
+            "------+-------+------",
+            "------------------",
+            "---------------------------",
+            "===========",
+        ];
+
+        for separator in separators.iter() {
+            let mut lines = vec!["# A comment".to_string(), "// Another one".to_string()];
+
+            for (i, row) in rows.iter().enumerate() {
+                if i == 3 || i == 6 {
+                    lines.push(separator.to_string());
+                }
+                lines.push(row.clone());
+            }
+
+            let grid = lines.join("\n");
+            assert_eq!(
+                grid.parse::<Sudoku>().unwrap().to_string(),
+                PUZZLE,
+                "{}",
+                grid
+            );
+        }
+    }
+
+    #[test]
+    fn too_long() {
+        let error = format!("{}\n-5", PUZZLE).parse::<Sudoku>().unwrap_err();
+
+        assert!(matches!(error, SudokuParseError::TooLong(_)));
+        assert_eq!(
+            error.location(),
+            Some(Location {
+                line: 10,
+                column: 1,
+                offset: 90,
+            })
+        );
+    }
+
+    #[test]
+    fn too_short() {
+        assert!(matches!(
+            PUZZLE[..80].parse::<Sudoku>(),
+            Err(SudokuParseError::TooShort(72))
+        ));
+    }
+
+    #[test]
+    fn invalid_char() {
+        let source = PUZZLE.replacen('1', "x", 2);
+        let error = source.parse::<Sudoku>().unwrap_err();
+
+        assert!(matches!(error, SudokuParseError::InvalidChar('x', _)));
+        assert_eq!(
+            error.location(),
+            Some(Location {
+                line: 2,
+                column: 5,
+                offset: 14,
+            })
+        );
+        assert_eq!(error.location().unwrap().caret(&source), "-3--x--5-\n    ^");
+    }
+
+    #[test]
+    fn plus_is_a_marker_in_progress() {
+        let source = PUZZLE.replacen("---6", "-+--6", 1);
+
+        assert_eq!(source.parse::<Sudoku>().unwrap().to_string(), PUZZLE);
+        assert!(matches!(
+            Sudoku::from_progress_str(&source),
+            Err(SudokuParseError::InvalidChar('+', _))
+        ));
+    }
+
+    #[test]
+    fn progress_round_trip() {
+        let mut sudoku = puzzle();
+        sudoku
+            .try_set(at(0, 2), Digit::Five, CellOrigin::Hint, Rules::Lenient)
+            .unwrap();
+        sudoku
+            .try_set(at(0, 0), Digit::Three, CellOrigin::Player, Rules::Lenient)
+            .unwrap();
+        sudoku.try_clear(at(0, 0)).unwrap();
+        sudoku
+            .try_set(at(0, 0), Digit::Two, CellOrigin::Player, Rules::Lenient)
+            .unwrap();
+        sudoku
+            .try_set(at(0, 1), Digit::Four, CellOrigin::Solver, Rules::Lenient)
+            .unwrap();
+        sudoku.set_as(at(0, 4), Some(Digit::Seven), CellOrigin::Imported);
+
+        let progress = sudoku.to_progress_string();
+        assert!(progress.starts_with("+2@4 *4@5 ?5@1 6~73---\n"));
+        assert!(progress.ends_with("\n---7-4---\n@5"));
+
+        let restored = Sudoku::from_progress_str(&progress).unwrap();
+        assert_eq!(restored.to_progress_string(), progress);
+        assert_eq!(restored.cells, sudoku.cells);
+        assert_eq!(restored.move_count(), 5);
+        assert_eq!(restored.count_origin(CellOrigin::Hint), 1);
+    }
+
+    #[test]
+    fn move_numbers_in_progress() {
+        let restored = Sudoku::from_progress_str(&PUZZLE.replacen("---6", "+2@7--6", 1)).unwrap();
+        assert_eq!(restored.get_raw(at(0, 0)).unwrap().placed_at, 7);
+        assert_eq!(restored.move_count(), 7);
+
+        for source in [
+            PUZZLE.replacen("---6", "2@7--6", 1),
+            PUZZLE.replacen("---6", "+2@--6", 1),
+            PUZZLE.replacen("---6", "-@7--6", 1),
+            format!("@3\n{}", PUZZLE),
+        ] {
+            assert!(matches!(
+                Sudoku::from_progress_str(&source),
+                Err(SudokuParseError::InvalidChar('@', _))
+            ));
+        }
+    }
+
+    #[test]
+    fn only_moves_are_counted() {
+        let mut sudoku = puzzle();
+        assert_eq!(
+            sudoku.try_set(at(0, 0), Digit::Two, CellOrigin::Given, Rules::Lenient),
+            Err(MoveError::Origin(CellOrigin::Given))
+        );
+        assert_eq!(
+            sudoku.try_set(at(0, 0), Digit::Two, CellOrigin::Imported, Rules::Lenient),
+            Err(MoveError::Origin(CellOrigin::Imported))
+        );
+        assert_eq!(sudoku.get_raw(at(0, 0)), None);
+
+        let solved = sudoku.solve().unwrap();
+        assert_eq!(solved.move_count(), 0);
+        assert!(solved
+            .cells
+            .iter()
+            .flatten()
+            .all(|cell| cell.placed_at == 0));
+
+        let generated = generator::Generator::new(7).generate().unwrap();
+        assert_eq!(generated.move_count(), 0);
+        assert_eq!(generated.minimize(&[]).unwrap().move_count(), 0);
+    }
+
+    #[test]
+    fn solver_leaves_notes_alone() {
+        let mut sudoku = puzzle();
+        sudoku.set_auto_notes(true);
+        let notes = sudoku.notes(at(0, 1));
+        let digit = notes.centre.first().unwrap();
+
+        sudoku.set(at(0, 0), Some(digit));
+        assert_eq!(sudoku.notes(at(0, 1)), notes);
+
+        sudoku
+            .try_set(at(0, 0), digit, CellOrigin::Player, Rules::Lenient)
+            .unwrap();
+        assert!(!sudoku.notes(at(0, 1)).centre.contains(digit));
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use super::{dlx::Dlx, grade::Grade, is_comment, solver::Backend, Sudoku, SudokuParseError};
+
+/// What to work out for every puzzle of a collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Task {
+    Solve,
+    /// Count the solutions, stopping once `limit` have been found
+    Count(usize),
+    Grade,
+}
+
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Solved(Option<Box<Sudoku>>),
+    Counted(usize),
+    /// `None` if the puzzle does not have exactly one solution
+    Graded(Option<Grade>),
+}
+
+/// The result for one line of a collection
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// The line number, starting at 1
+    pub line: usize,
+    pub outcome: Result<Outcome, SudokuParseError>,
+}
+
+/// The results for a whole collection, in the order of its lines
+#[derive(Debug, Clone)]
+pub struct Batch {
+    pub entries: Vec<Entry>,
+    pub elapsed: Duration,
+}
+
+impl Batch {
+    /// Reads a collection with one puzzle per line and works through it on all
+    /// cores. Empty lines and comments are skipped.
+    pub fn run(collection: &str, task: Task) -> Self {
+        let start = Instant::now();
+        let lines: Vec<_> = collection
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !is_comment(line))
+            .collect();
+
+        let entries = lines
+            .into_par_iter()
+            .map(|(i, line)| Entry {
+                line: i + 1,
+                outcome: line.trim().parse().map(|puzzle| Self::work(&puzzle, task)),
+            })
+            .collect();
+
+        Batch {
+            entries,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    fn work(puzzle: &Sudoku, task: Task) -> Outcome {
+        match task {
+            Task::Solve => Outcome::Solved(Dlx::new().solve(puzzle).map(Box::new)),
+            Task::Count(limit) => Outcome::Counted(Dlx::new().count_solutions(puzzle, limit)),
+            Task::Grade => Outcome::Graded(puzzle.grade()),
+        }
+    }
+
+    /// The lines which could not be read as a puzzle
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &SudokuParseError)> {
+        self.entries.iter().filter_map(|entry| {
+            entry
+                .outcome
+                .as_ref()
+                .err()
+                .map(|error| (entry.line, error))
+        })
+    }
+
+    pub fn puzzles_per_second(&self) -> f64 {
+        self.entries.len() as f64 / self.elapsed.as_secs_f64()
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    convert::TryFrom,
+    fmt::{self, Debug},
+    iter::FromIterator,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub, SubAssign},
+};
+
+use super::Digit;
+
+/// A set of digits stored as a bitmask, bit `n` standing for the digit `n`
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Candidates(u16);
+
+impl Candidates {
+    const MASK: u16 = 0b11_1111_1110;
+
+    pub const fn none() -> Self {
+        Candidates(0)
+    }
+
+    pub const fn all() -> Self {
+        Candidates(Self::MASK)
+    }
+
+    pub fn single(digit: Digit) -> Self {
+        Candidates(1 << u8::from(digit))
+    }
+
+    pub fn bits(self) -> u16 {
+        self.0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, digit: Digit) -> bool {
+        self.0 & Self::single(digit).0 != 0
+    }
+
+    /// Adds `digit`, returning whether it was not in the set yet
+    pub fn insert(&mut self, digit: Digit) -> bool {
+        let is_new = !self.contains(digit);
+        self.0 |= Self::single(digit).0;
+        is_new
+    }
+
+    /// Removes `digit`, returning whether it was in the set
+    pub fn remove(&mut self, digit: Digit) -> bool {
+        let was_present = self.contains(digit);
+        self.0 &= !Self::single(digit).0;
+        was_present
+    }
+
+    pub fn clear(&mut self) {
+        self.0 = 0;
+    }
+
+    /// The smallest digit in the set
+    pub fn first(self) -> Option<Digit> {
+        self.iter().next()
+    }
+
+    pub fn is_subset(self, other: Candidates) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// The digits in ascending order
+    pub fn iter(self) -> Iter {
+        Iter(self.0)
+    }
+}
+
+impl Debug for Candidates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<Digit> for Candidates {
+    fn from_iter<I: IntoIterator<Item = Digit>>(digits: I) -> Self {
+        let mut candidates = Candidates::none();
+
+        for digit in digits {
+            candidates.insert(digit);
+        }
+
+        candidates
+    }
+}
+
+impl Extend<Digit> for Candidates {
+    fn extend<I: IntoIterator<Item = Digit>>(&mut self, digits: I) {
+        for digit in digits {
+            self.insert(digit);
+        }
+    }
+}
+
+impl IntoIterator for Candidates {
+    type Item = Digit;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+/// Iterator over the digits of [`Candidates`]
+#[derive(Debug, Clone)]
+pub struct Iter(u16);
+
+impl Iterator for Iter {
+    type Item = Digit;
+
+    fn next(&mut self) -> Option<Digit> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let digit = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+
+        Digit::try_from(digit).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+impl BitOr for Candidates {
+    type Output = Candidates;
+
+    fn bitor(self, other: Candidates) -> Candidates {
+        Candidates(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Candidates {
+    fn bitor_assign(&mut self, other: Candidates) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for Candidates {
+    type Output = Candidates;
+
+    fn bitand(self, other: Candidates) -> Candidates {
+        Candidates(self.0 & other.0)
+    }
+}
+
+impl BitAndAssign for Candidates {
+    fn bitand_assign(&mut self, other: Candidates) {
+        self.0 &= other.0;
+    }
+}
+
+impl Sub for Candidates {
+    type Output = Candidates;
+
+    fn sub(self, other: Candidates) -> Candidates {
+        Candidates(self.0 & !other.0)
+    }
+}
+
+impl SubAssign for Candidates {
+    fn sub_assign(&mut self, other: Candidates) {
+        self.0 &= !other.0;
+    }
+}
+
+impl Not for Candidates {
+    type Output = Candidates;
+
+    /// Every digit not in the set
+    fn not(self) -> Candidates {
+        Candidates(!self.0 & Self::MASK)
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt::{self, Display};
+
+use super::{Digit, House, Position, Relation, Sudoku};
+
+/// A digit which is in a house more than once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub digit: Digit,
+    /// Every cell of the house holding the digit
+    pub cells: Vec<Position>,
+    pub relation: Relation,
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let house = match self.relation {
+            Relation::Row => "row",
+            Relation::Column => "column",
+            Relation::Block => "block",
+        };
+
+        write!(
+            f,
+            "{} appears {} times in the same {}:",
+            u8::from(self.digit),
+            self.cells.len(),
+            house
+        )?;
+
+        for (i, cell) in self.cells.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { "" } else { "," }, cell)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Sudoku {
+    /// Every digit placed more than once in a row, column or block, ordered by
+    /// house and then by digit
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+
+        for house in House::all() {
+            for digit in Digit::iterator() {
+                let cells: Vec<_> = house
+                    .cells()
+                    .filter(|&pos| self.get_raw(pos).map_or(false, |cell| cell.value == digit))
+                    .collect();
+
+                if cells.len() > 1 {
+                    conflicts.push(Conflict {
+                        digit,
+                        cells,
+                        relation: house.relation(),
+                    });
+                }
+            }
+        }
+
+        conflicts
+    }
+}
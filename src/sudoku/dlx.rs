@@ -0,0 +1,477 @@
+use std::iter;
+
+use super::{solver::Backend, Digit, Position, Sudoku};
+
+/// Exact cover solver using Knuth's Dancing Links
+///
+/// Every placement of a digit in a cell is a row of the matrix, which covers
+/// four of the 324 constraints: the cell has a digit, and its row, column and
+/// block each have that digit once. Variant sudokus add constraints of their
+/// own with [`Dlx::house`] and [`Dlx::distinct`].
+#[derive(Debug, Default, Clone)]
+pub struct Dlx {
+    houses: Vec<Vec<Position>>,
+    distinct: Vec<Vec<Position>>,
+}
+
+impl Dlx {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds 9 cells which have to contain every digit once, like the diagonals
+    /// of an X-sudoku
+    pub fn house(mut self, cells: [Position; 9]) -> Self {
+        self.houses.push(cells.to_vec());
+        self
+    }
+
+    /// Adds cells which can not contain the same digit twice, but do not have
+    /// to contain every digit
+    pub fn distinct(mut self, cells: &[Position]) -> Self {
+        self.distinct.push(cells.to_vec());
+        self
+    }
+
+    /// Every solution of the board, in no particular order
+    pub fn solutions<'a>(&self, sudoku: &'a Sudoku) -> Solutions<'a> {
+        Solutions {
+            sudoku,
+            search: Search::new(self, sudoku),
+        }
+    }
+
+    fn primary_columns(&self) -> usize {
+        324 + self.houses.len() * 9
+    }
+
+    /// The columns covered by placing `digit` at `position`
+    fn columns(&self, position: Position, digit: Digit) -> Vec<usize> {
+        let d = u8::from(digit) as usize - 1;
+
+        let mut columns = vec![
+            position.index(),
+            81 + position.row() * 9 + d,
+            162 + position.column() * 9 + d,
+            243 + position.block() * 9 + d,
+        ];
+
+        for (i, house) in self.houses.iter().enumerate() {
+            if house.contains(&position) {
+                columns.push(324 + i * 9 + d);
+            }
+        }
+
+        for (i, cells) in self.distinct.iter().enumerate() {
+            if cells.contains(&position) {
+                columns.push(self.primary_columns() + i * 9 + d);
+            }
+        }
+
+        columns
+    }
+}
+
+impl Backend for Dlx {
+    fn solve(&self, sudoku: &Sudoku) -> Option<Sudoku> {
+        self.solutions(sudoku).next()
+    }
+
+    fn count_solutions(&self, sudoku: &Sudoku, limit: usize) -> usize {
+        let mut search = Search::new(self, sudoku);
+        let mut count = 0;
+
+        while count < limit && search.next_solution() {
+            count += 1;
+        }
+
+        count
+    }
+}
+
+/// Iterator over the solutions of a board, see [`Dlx::solutions`]
+#[derive(Debug, Clone)]
+pub struct Solutions<'a> {
+    sudoku: &'a Sudoku,
+    search: Search,
+}
+
+impl Iterator for Solutions<'_> {
+    type Item = Sudoku;
+
+    fn next(&mut self) -> Option<Sudoku> {
+        if !self.search.next_solution() {
+            return None;
+        }
+
+        let mut solution = self.sudoku.clone();
+        for (pos, digit) in self.search.placements() {
+            solution.set(pos, Some(digit));
+        }
+
+        Some(solution)
+    }
+}
+
+/// The sparse matrix as circular doubly linked lists, with the search stack
+///
+/// Node 0 is the root, nodes 1 to `columns` are the column headers and the
+/// rest are the 1s of the matrix. Only primary columns are linked into the
+/// header list, so the search never has to cover secondary ones.
+#[derive(Debug, Clone)]
+struct Search {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    size: Vec<usize>,
+    placement: Vec<(Position, Digit)>,
+    /// A node of each selected row, starting with the given cells
+    stack: Vec<usize>,
+    givens: usize,
+    state: State,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Searching,
+    Found,
+    Done,
+}
+
+impl Search {
+    fn new(dlx: &Dlx, sudoku: &Sudoku) -> Self {
+        let primary = dlx.primary_columns();
+        let columns = primary + dlx.distinct.len() * 9;
+
+        let mut search = Search {
+            left: Vec::new(),
+            right: Vec::new(),
+            up: Vec::new(),
+            down: Vec::new(),
+            column: Vec::new(),
+            size: vec![0; columns + 1],
+            placement: Vec::new(),
+            stack: Vec::new(),
+            givens: 0,
+            state: State::Searching,
+        };
+
+        // Headers have no placement, so they get one that is never read
+        let header = (
+            Position::new(0, 0).expect("r1c1 is on the board"),
+            Digit::One,
+        );
+
+        for node in 0..=columns {
+            if node > primary {
+                search.left.push(node);
+                search.right.push(node);
+            } else {
+                search.left.push(if node == 0 { primary } else { node - 1 });
+                search
+                    .right
+                    .push(if node == primary { 0 } else { node + 1 });
+            }
+
+            search.up.push(node);
+            search.down.push(node);
+            search.column.push(node);
+            search.placement.push(header);
+        }
+        let mut givens = Vec::new();
+
+        for (position, cell) in sudoku.all_raw() {
+            for digit in Digit::iterator() {
+                if cell.map_or(false, |cell| cell.value != digit) {
+                    continue;
+                }
+
+                let first = search.add_row(dlx.columns(position, digit), (position, digit));
+
+                if cell.is_some() {
+                    givens.push(first);
+                }
+            }
+        }
+
+        let mut used = vec![false; columns + 1];
+
+        for first in givens {
+            let row: Vec<_> = search.row(first).map(|node| search.column[node]).collect();
+
+            if row.iter().any(|&column| used[column]) {
+                search.state = State::Done;
+                break;
+            }
+
+            for &column in row.iter() {
+                used[column] = true;
+            }
+
+            search.cover(search.column[first]);
+            search.select(first);
+        }
+        search.givens = search.stack.len();
+
+        search
+    }
+
+    fn add_row(&mut self, columns: Vec<usize>, placement: (Position, Digit)) -> usize {
+        let first = self.column.len();
+
+        for (i, &column) in columns.iter().enumerate() {
+            let node = first + i;
+            let last = first + columns.len() - 1;
+
+            self.left.push(if i == 0 { last } else { node - 1 });
+            self.right.push(if node == last { first } else { node + 1 });
+            self.up.push(self.up[column + 1]);
+            self.down.push(column + 1);
+            self.column.push(column + 1);
+            self.placement.push(placement);
+
+            let above = self.up[column + 1];
+            self.down[above] = node;
+            self.up[column + 1] = node;
+            self.size[column + 1] += 1;
+        }
+
+        first
+    }
+
+    /// The nodes of the row containing `node`, starting with `node`
+    fn row(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        iter::once(node).chain(
+            iter::successors(Some(self.right[node]), move |&next| Some(self.right[next]))
+                .take_while(move |&next| next != node),
+        )
+    }
+
+    fn cover(&mut self, column: usize) {
+        let (left, right) = (self.left[column], self.right[column]);
+        self.right[left] = right;
+        self.left[right] = left;
+
+        let mut i = self.down[column];
+        while i != column {
+            let mut j = self.right[i];
+            while j != i {
+                let (up, down) = (self.up[j], self.down[j]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.size[self.column[j]] -= 1;
+
+                j = self.right[j];
+            }
+
+            i = self.down[i];
+        }
+    }
+
+    fn uncover(&mut self, column: usize) {
+        let mut i = self.up[column];
+        while i != column {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.column[j]] += 1;
+                let (up, down) = (self.up[j], self.down[j]);
+                self.down[up] = j;
+                self.up[down] = j;
+
+                j = self.left[j];
+            }
+
+            i = self.up[i];
+        }
+
+        let (left, right) = (self.left[column], self.right[column]);
+        self.right[left] = column;
+        self.left[right] = column;
+    }
+
+    /// Adds the row of `node` to the solution, covering the rest of its
+    /// columns
+    fn select(&mut self, node: usize) {
+        self.stack.push(node);
+
+        let mut j = self.right[node];
+        while j != node {
+            self.cover(self.column[j]);
+            j = self.right[j];
+        }
+    }
+
+    /// Undoes [`Search::select`] for the most recently selected row
+    fn unselect(&mut self) -> Option<usize> {
+        let node = self.stack.pop()?;
+
+        let mut j = self.left[node];
+        while j != node {
+            self.uncover(self.column[j]);
+            j = self.left[j];
+        }
+
+        Some(node)
+    }
+
+    /// Replaces the most recently selected row with the next one in its
+    /// column, backtracking until there is one
+    fn advance(&mut self) -> bool {
+        while self.stack.len() > self.givens {
+            let node = self.unselect().expect("the stack is not empty");
+            let column = self.column[node];
+
+            let next = self.down[node];
+            if next != column {
+                self.select(next);
+                return true;
+            }
+
+            self.uncover(column);
+        }
+
+        false
+    }
+
+    /// Continues the search up to the next solution
+    fn next_solution(&mut self) -> bool {
+        match self.state {
+            State::Done => return false,
+            State::Found => {
+                if !self.advance() {
+                    self.state = State::Done;
+                    return false;
+                }
+            }
+            State::Searching => {}
+        }
+
+        loop {
+            if self.right[0] == 0 {
+                self.state = State::Found;
+                return true;
+            }
+
+            let column = self.smallest_column();
+            self.cover(column);
+
+            let first = self.down[column];
+            if first != column {
+                self.select(first);
+                continue;
+            }
+
+            self.uncover(column);
+            if !self.advance() {
+                self.state = State::Done;
+                return false;
+            }
+        }
+    }
+
+    /// The primary column with the fewest rows left
+    fn smallest_column(&self) -> usize {
+        let mut smallest = self.right[0];
+
+        let mut column = self.right[smallest];
+        while column != 0 {
+            if self.size[column] < self.size[smallest] {
+                smallest = column;
+            }
+            column = self.right[column];
+        }
+
+        smallest
+    }
+
+    /// The digits placed by the search, without the given cells
+    fn placements(&self) -> impl Iterator<Item = (Position, Digit)> + '_ {
+        self.stack[self.givens..]
+            .iter()
+            .map(move |&node| self.placement[node])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::{solver::Backtracking, Candidates, SolveStatus};
+
+    const PUZZLES: [&str; 4] = [
+        // Needs guessing
+        "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......",
+        "52...6.........7.13...........4..8..6......5...........418.........3..2...87.....",
+        // Two solutions
+        "4-7-6982-6-2-589479587243168254371697915-6432346912758289-43571573291684-64875293",
+        // Unsolvable, r1c1 and r2c1 can only be a 2
+        ".13456789.56...4..789............................................................",
+    ];
+
+    fn diagonals(dlx: Dlx) -> Dlx {
+        let mut main = [Position::new(0, 0).unwrap(); 9];
+        let mut anti = main;
+
+        for i in 0..9 {
+            main[i] = Position::new(i, i).unwrap();
+            anti[i] = Position::new(i, 8 - i).unwrap();
+        }
+
+        dlx.house(main).house(anti)
+    }
+
+    #[test]
+    fn agrees_with_backtracking() {
+        for puzzle in PUZZLES.iter() {
+            let sudoku: Sudoku = puzzle.parse().unwrap();
+
+            assert_eq!(
+                Dlx::new().count_solutions(&sudoku, 10),
+                Backtracking.count_solutions(&sudoku, 10),
+                "{}",
+                puzzle
+            );
+
+            if Backtracking.has_unique_solution(&sudoku) {
+                assert_eq!(
+                    Dlx::new().solve(&sudoku).map(|s| s.to_line_string()),
+                    Backtracking.solve(&sudoku).map(|s| s.to_line_string()),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn counts_are_limited() {
+        let sudoku: Sudoku = PUZZLES[2].parse().unwrap();
+
+        assert_eq!(Dlx::new().count_solutions(&sudoku, 2), 2);
+        assert_eq!(Dlx::new().solutions(&sudoku).count(), 2);
+    }
+
+    #[test]
+    fn diagonal_variant() {
+        // An X-sudoku, which has many solutions without its diagonals
+        let sudoku: Sudoku =
+            "1--------7-9-2--5-4---89---6-7---9------3------2----3----9---1------5---------3-4"
+                .parse()
+                .unwrap();
+        let dlx = diagonals(Dlx::new());
+
+        assert_eq!(Dlx::new().count_solutions(&sudoku, 10), 10);
+        assert_eq!(Backtracking.count_solutions(&sudoku, 10), 10);
+        assert_eq!(dlx.count_solutions(&sudoku, 10), 1);
+
+        let solution = dlx.solve(&sudoku).unwrap();
+        assert_eq!(solution.status(), SolveStatus::Solved);
+
+        for cells in dlx.houses.iter() {
+            let digits: Candidates = cells
+                .iter()
+                .map(|&pos| solution.get_raw(pos).unwrap().value)
+                .collect();
+            assert_eq!(digits, Candidates::all());
+        }
+    }
+}
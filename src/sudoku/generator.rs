@@ -0,0 +1,205 @@
+use std::fmt::{self, Display};
+
+use super::{grade::Difficulty, Digit, Position, Sudoku};
+
+/// How the clues of a generated puzzle mirror each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    None,
+    /// Clues are kept when turning the board by 180°
+    Rotational,
+    /// Clues are mirrored between the left and right halves of the board
+    Mirror,
+    /// Clues are mirrored across the main diagonal
+    Diagonal,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 4] = [
+        Symmetry::None,
+        Symmetry::Rotational,
+        Symmetry::Mirror,
+        Symmetry::Diagonal,
+    ];
+
+    /// The cell which has to match `position`
+    fn image(self, position: Position) -> Position {
+        let (row, column) = (position.row(), position.column());
+
+        match self {
+            Symmetry::None => Some(position),
+            Symmetry::Rotational => Position::new(8 - row, 8 - column),
+            Symmetry::Mirror => Position::new(row, 8 - column),
+            Symmetry::Diagonal => Position::new(column, row),
+        }
+        .expect("image is on the board")
+    }
+}
+
+impl Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Symmetry::None => "none",
+            Symmetry::Rotational => "rotational",
+            Symmetry::Mirror => "mirror",
+            Symmetry::Diagonal => "diagonal",
+        })
+    }
+}
+
+/// A small, seedable random number generator (splitmix64), so that the same
+/// seed always generates the same puzzle
+#[derive(Debug, Clone)]
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Generates puzzles with a unique solution by removing clues from a random
+/// solved board
+#[derive(Debug, Clone)]
+pub struct Generator {
+    random: Random,
+    symmetry: Symmetry,
+    difficulty: Option<Difficulty>,
+    attempts: usize,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Generator {
+            random: Random(seed),
+            symmetry: Symmetry::None,
+            difficulty: None,
+            attempts: 50,
+        }
+    }
+
+    pub fn symmetry(mut self, symmetry: Symmetry) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    /// Only generate puzzles of the given difficulty
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = Some(difficulty);
+        self
+    }
+
+    /// How many boards to try before giving up on reaching the difficulty
+    pub fn attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Generates the next puzzle, or `None` if none of the attempts reached the
+    /// requested difficulty
+    pub fn generate(&mut self) -> Option<Sudoku> {
+        for _ in 0..self.attempts {
+            let solution = self.solved_board();
+            let puzzle = self.remove_clues(solution);
+
+            match self.difficulty {
+                None => return Some(puzzle),
+                Some(difficulty) => {
+                    if puzzle.grade().map(|grade| grade.difficulty) == Some(difficulty) {
+                        return Some(puzzle);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn solved_board(&mut self) -> Sudoku {
+        let mut sudoku = Sudoku::new();
+        let order: Vec<_> = Position::all().collect();
+
+        if !self.fill(&mut sudoku, &order) {
+            unreachable!("an empty board always has a solution");
+        }
+
+        sudoku
+    }
+
+    fn fill(&mut self, sudoku: &mut Sudoku, order: &[Position]) -> bool {
+        let (pos, rest) = match order.split_first() {
+            Some(split) => split,
+            None => return true,
+        };
+
+        let mut digits: Vec<Digit> = sudoku.possibilities(*pos).into_iter().collect();
+        self.random.shuffle(&mut digits);
+
+        for digit in digits {
+            sudoku.set_given(*pos, digit);
+
+            if self.fill(sudoku, rest) {
+                return true;
+            }
+        }
+
+        sudoku.set(*pos, None);
+
+        false
+    }
+
+    fn remove_clues(&mut self, mut puzzle: Sudoku) -> Sudoku {
+        let mut orbits = Vec::new();
+        for pos in Position::all() {
+            let image = self.symmetry.image(pos);
+
+            if pos <= image {
+                orbits.push((pos, image));
+            }
+        }
+        self.random.shuffle(&mut orbits);
+
+        for (pos, image) in orbits {
+            let removed = [(pos, puzzle.get_raw(pos)), (image, puzzle.get_raw(image))];
+
+            puzzle.set(pos, None);
+            puzzle.set(image, None);
+
+            if !self.is_acceptable(&puzzle) {
+                for &(pos, cell) in removed.iter() {
+                    if let Some(cell) = cell {
+                        puzzle.set_given(pos, cell.value);
+                    }
+                }
+            }
+        }
+
+        puzzle
+    }
+
+    /// The puzzle still has a unique solution, and is not harder than the
+    /// requested difficulty
+    fn is_acceptable(&self, puzzle: &Sudoku) -> bool {
+        match self.difficulty {
+            None | Some(Difficulty::Expert) => puzzle.has_unique_solution(),
+            Some(difficulty) => puzzle.grade().map_or(false, |grade| {
+                !grade.needs_guessing && grade.difficulty <= difficulty
+            }),
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use super::{
+    logic::{Deduction, LogicalSolver, Technique},
+    Sudoku,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+
+    /// The difficulty of a puzzle whose hardest step has the given rating
+    pub fn from_rating(rating: f64) -> Self {
+        if rating <= 2.3 {
+            Difficulty::Easy
+        } else if rating <= 3.4 {
+            Difficulty::Medium
+        } else if rating <= 4.6 {
+            Difficulty::Hard
+        } else {
+            Difficulty::Expert
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+            Difficulty::Expert => "Expert",
+        })
+    }
+}
+
+impl Technique {
+    /// The rating of the technique on the Sudoku Explainer scale
+    ///
+    /// Techniques Sudoku Explainer does not know about are rated next to the
+    /// ones it uses to make the same kind of deduction
+    pub fn rating(self) -> f64 {
+        match self {
+            Technique::HiddenSingle => 1.2,
+            Technique::NakedSingle => 2.3,
+            Technique::Pointing => 2.6,
+            Technique::Claiming => 2.8,
+            Technique::NakedPair => 3.0,
+            Technique::XWing => 3.2,
+            Technique::HiddenPair => 3.4,
+            Technique::NakedTriple => 3.6,
+            Technique::Swordfish => 3.8,
+            Technique::HiddenTriple => 4.0,
+            Technique::XYWing => 4.2,
+            Technique::XYZWing => 4.4,
+            Technique::WWing => 4.4,
+            Technique::FinnedXWing => 4.5,
+            Technique::SashimiXWing => 4.6,
+            Technique::NakedQuad => 5.0,
+            Technique::Jellyfish => 5.2,
+            Technique::FinnedSwordfish => 5.3,
+            Technique::HiddenQuad => 5.4,
+            Technique::SashimiSwordfish => 5.4,
+            Technique::FinnedJellyfish => 5.6,
+            Technique::SashimiJellyfish => 5.7,
+            Technique::SimpleColoring => 6.5,
+            Technique::XCycle => 6.5,
+            Technique::XChain => 6.6,
+            Technique::XYChain => 6.6,
+            Technique::AIC => 7.0,
+            Technique::GroupedAIC => 7.3,
+        }
+    }
+}
+
+impl Deduction {
+    /// The rating of the technique used, plus Sudoku Explainer's extra rating
+    /// for long chains
+    pub fn rating(&self) -> f64 {
+        let mut rating = self.technique.rating();
+
+        if !self.chain.is_empty() {
+            let mut ceiling = 4;
+            let mut is_odd = false;
+
+            while self.chain.len().saturating_sub(2) > ceiling {
+                rating += 0.1;
+                ceiling = if is_odd {
+                    ceiling * 4 / 3
+                } else {
+                    ceiling * 3 / 2
+                };
+                is_odd = !is_odd;
+            }
+        }
+
+        rating
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grade {
+    pub difficulty: Difficulty,
+    /// The rating of the hardest step, on the Sudoku Explainer scale
+    pub rating: f64,
+    pub hardest: Option<Technique>,
+    /// How many times each technique was used
+    pub techniques: BTreeMap<Technique, usize>,
+    /// The logical solver got stuck, so the puzzle can only be finished by
+    /// guessing and backtracking
+    pub needs_guessing: bool,
+}
+
+impl Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "difficulty: {}", self.difficulty)?;
+        writeln!(f, "rating: {:.1}", self.rating)?;
+
+        if let Some(hardest) = self.hardest {
+            writeln!(f, "hardest technique: {}", hardest)?;
+        }
+
+        if self.needs_guessing {
+            writeln!(f, "needs guessing")?;
+        }
+
+        for (technique, count) in self.techniques.iter() {
+            writeln!(f, "    {}: {}", technique, count)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Sudoku {
+    /// Grades the puzzle by solving it with the [`LogicalSolver`], or returns
+    /// `None` if it does not have exactly one solution
+    pub fn grade(&self) -> Option<Grade> {
+        if !self.has_unique_solution() {
+            return None;
+        }
+
+        let mut solver = LogicalSolver::new(self.clone());
+        let mut techniques = BTreeMap::new();
+        let mut hardest: Option<(Technique, f64)> = None;
+
+        for deduction in solver.solve() {
+            *techniques.entry(deduction.technique).or_insert(0) += 1;
+
+            let rating = deduction.rating();
+            if hardest.map_or(true, |(_, hardest)| rating > hardest) {
+                hardest = Some((deduction.technique, rating));
+            }
+        }
+
+        let needs_guessing = !solver.is_solved();
+        let rating = hardest.map_or(0.0, |(_, rating)| rating);
+
+        Some(Grade {
+            difficulty: if needs_guessing {
+                Difficulty::Expert
+            } else {
+                Difficulty::from_rating(rating)
+            },
+            rating,
+            hardest: hardest.map(|(technique, _)| technique),
+            techniques,
+            needs_guessing,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn techniques_are_tried_easiest_first() {
+        for pair in Technique::ALL.windows(2) {
+            assert!(pair[0].rating() <= pair[1].rating(), "{:?}", pair);
+        }
+    }
+
+    #[test]
+    fn known_ratings() {
+        let puzzles = [
+            (
+                "---6-3----3--1--5---9---2--7--1-6--9-2-----8-1--4-9--3--8---1---5--9--7----7-4---",
+                "1.2",
+                Difficulty::Easy,
+                Technique::HiddenSingle,
+            ),
+            (
+                "000000907000420180000705026100904000050000040000507009920108000034059000507000000",
+                "2.3",
+                Difficulty::Easy,
+                Technique::NakedSingle,
+            ),
+            (
+                "001900003900700160030005007050000009004302600200000070600100030042007006500006800",
+                "3.0",
+                Difficulty::Medium,
+                Technique::NakedPair,
+            ),
+            (
+                "-3-5----4---3279-------6-----6----7-2------1----48---5----95--2--7---3---14----6-",
+                "4.2",
+                Difficulty::Hard,
+                Technique::XYWing,
+            ),
+            // Simple Coloring also works, but a Finned X-Wing is easier
+            (
+                "--1--4-5--3------6---5-19--9-----4-27------3--139-------84----3-5--2----16---5---",
+                "4.5",
+                Difficulty::Hard,
+                Technique::FinnedXWing,
+            ),
+            (
+                "9-3---8-4--2--9----476---------8-9--3--4-6-1--------86---82--6-7-4---2-----93----",
+                "6.7",
+                Difficulty::Expert,
+                Technique::XYChain,
+            ),
+        ];
+
+        for (puzzle, rating, difficulty, hardest) in puzzles.iter() {
+            let grade = puzzle.parse::<Sudoku>().unwrap().grade().unwrap();
+
+            assert_eq!(format!("{:.1}", grade.rating), *rating, "{}", puzzle);
+            assert_eq!(grade.difficulty, *difficulty, "{}", puzzle);
+            assert_eq!(grade.hardest, Some(*hardest), "{}", puzzle);
+            assert!(!grade.needs_guessing);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::mem;
+
+use super::{Cell, CellOrigin, Digit, MoveError, NoteMode, Notes, Position, Rules, Sudoku};
+
+/// A [`Sudoku`] along with every change made to it, so that changes can be
+/// undone and redone
+///
+/// Changes made between [`History::begin_group`] and [`History::end_group`]
+/// are undone and redone together
+#[derive(Debug, Clone)]
+pub struct History {
+    sudoku: Sudoku,
+    undo: Vec<Action>,
+    redo: Vec<Action>,
+    group: Option<Action>,
+}
+
+/// One undoable step, made up of the changes of one or more cells. Turning
+/// auto-notes on or off is a setting rather than a change, see
+/// [`History::set_auto_notes`].
+#[derive(Debug, Clone)]
+pub struct Action {
+    pub changes: Vec<Change>,
+    /// The move count of the board before and after the action
+    moves: (u32, u32),
+}
+
+/// A cell going from `before` to `after`, along with its notes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Change {
+    pub position: Position,
+    pub before: Option<Cell>,
+    pub after: Option<Cell>,
+    pub notes_before: Notes,
+    pub notes_after: Notes,
+}
+
+impl Change {
+    fn is_empty(&self) -> bool {
+        self.before == self.after && self.notes_before == self.notes_after
+    }
+}
+
+impl History {
+    pub fn new(sudoku: Sudoku) -> Self {
+        History {
+            sudoku,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            group: None,
+        }
+    }
+
+    pub fn sudoku(&self) -> &Sudoku {
+        &self.sudoku
+    }
+
+    pub fn into_sudoku(self) -> Sudoku {
+        self.sudoku
+    }
+
+    /// [`Sudoku::try_set`], recorded as an action
+    pub fn try_set(
+        &mut self,
+        position: Position,
+        digit: Digit,
+        origin: CellOrigin,
+        rules: Rules,
+    ) -> Result<(), MoveError> {
+        self.edit(|sudoku| sudoku.try_set(position, digit, origin, rules))
+    }
+
+    /// [`Sudoku::try_clear`], recorded as an action
+    pub fn try_clear(&mut self, position: Position) -> Result<(), MoveError> {
+        self.edit(|sudoku| sudoku.try_clear(position))
+    }
+
+    /// [`Sudoku::set_auto_notes`], which is a setting rather than a change, so
+    /// neither it nor the notes it fills in are recorded
+    pub fn set_auto_notes(&mut self, enabled: bool) {
+        self.sudoku.set_auto_notes(enabled);
+    }
+
+    /// [`Sudoku::toggle_note`], recorded as an action
+    pub fn toggle_note(&mut self, position: Position, digit: Digit, mode: NoteMode) -> bool {
+        self.edit(|sudoku| sudoku.toggle_note(position, digit, mode))
+    }
+
+    /// Runs `edit` on the board and records every cell it changed as a single
+    /// action, or as part of the open group
+    pub fn edit<T>(&mut self, edit: impl FnOnce(&mut Sudoku) -> T) -> T {
+        let cells = self.sudoku.cells;
+        let notes = self.sudoku.notes;
+        let moves = self.sudoku.moves;
+
+        let result = edit(&mut self.sudoku);
+
+        let changes: Vec<_> = Position::all()
+            .map(|position| Change {
+                position,
+                before: cells[position.index()],
+                after: self.sudoku.get_raw(position),
+                notes_before: notes[position.index()],
+                notes_after: self.sudoku.notes(position),
+            })
+            .filter(|change| !change.is_empty())
+            .collect();
+
+        if !changes.is_empty() {
+            self.push(Action {
+                changes,
+                moves: (moves, self.sudoku.moves),
+            });
+        }
+
+        result
+    }
+
+    fn push(&mut self, action: Action) {
+        self.redo.clear();
+
+        let group = match &mut self.group {
+            Some(group) => group,
+            None => return self.undo.push(action),
+        };
+
+        // A group only keeps the first and last state of each cell
+        for change in action.changes {
+            match group
+                .changes
+                .iter_mut()
+                .find(|other| other.position == change.position)
+            {
+                Some(other) => {
+                    other.after = change.after;
+                    other.notes_after = change.notes_after;
+                }
+                None => group.changes.push(change),
+            }
+        }
+        group.changes.retain(|change| !change.is_empty());
+        group.moves.1 = action.moves.1;
+    }
+
+    /// Starts collecting changes into a single action, for example all of the
+    /// steps of a solver
+    pub fn begin_group(&mut self) {
+        self.end_group();
+        self.group = Some(Action {
+            changes: Vec::new(),
+            moves: (self.sudoku.moves, self.sudoku.moves),
+        });
+    }
+
+    pub fn end_group(&mut self) {
+        if let Some(group) = self.group.take().filter(|group| !group.changes.is_empty()) {
+            self.undo.push(group);
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverts the last action, returning whether there was one
+    pub fn undo(&mut self) -> bool {
+        self.end_group();
+
+        match self.undo.pop() {
+            Some(action) => {
+                self.restore(action.changes.iter().rev(), |change| {
+                    (change.before, change.notes_before)
+                });
+                self.sudoku.moves = action.moves.0;
+
+                self.redo.push(action);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Applies the last undone action again, returning whether there was one
+    pub fn redo(&mut self) -> bool {
+        self.end_group();
+
+        match self.redo.pop() {
+            Some(action) => {
+                self.restore(action.changes.iter(), |change| {
+                    (change.after, change.notes_after)
+                });
+                self.sudoku.moves = action.moves.1;
+
+                self.undo.push(action);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Puts back one side of every change. Auto-notes are held off, as the
+    /// notes are restored along with the cells.
+    fn restore<'a>(
+        &mut self,
+        changes: impl Iterator<Item = &'a Change>,
+        state: impl Fn(&Change) -> (Option<Cell>, Notes),
+    ) {
+        let auto_notes = mem::replace(&mut self.sudoku.auto_notes, false);
+
+        for change in changes {
+            let (cell, notes) = state(change);
+
+            self.sudoku.replace(change.position, cell);
+            self.sudoku.set_notes(change.position, notes);
+        }
+
+        self.sudoku.auto_notes = auto_notes;
+    }
+
+    /// Undoes actions until the board can be solved again, returning how many
+    /// were undone
+    pub fn revert_to_last_correct(&mut self) -> usize {
+        let mut undone = 0;
+
+        while self.sudoku.count_solutions(1) == 0 && self.undo() {
+            undone += 1;
+        }
+
+        undone
+    }
+}
+
+impl From<Sudoku> for History {
+    fn from(sudoku: Sudoku) -> Self {
+        Self::new(sudoku)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_notes_are_not_undone() {
+        let sudoku =
+            "---6-3----3--1--5---9---2--7--1-6--9-2-----8-1--4-9--3--8---1---5--9--7----7-4---"
+                .parse()
+                .unwrap();
+        let mut history = History::new(sudoku);
+        let position = Position::new(0, 0).unwrap();
+
+        history.set_auto_notes(true);
+        assert!(!history.can_undo());
+
+        let notes = history.sudoku().notes(position);
+        let digit = notes.centre.first().unwrap();
+        assert!(!notes.is_empty());
+
+        history
+            .try_set(position, digit, CellOrigin::Player, Rules::Lenient)
+            .unwrap();
+        assert!(history.undo());
+        assert!(!history.undo());
+
+        assert!(history.sudoku().auto_notes());
+        assert_eq!(history.sudoku().notes(position), notes);
+    }
+}
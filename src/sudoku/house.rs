@@ -0,0 +1,98 @@
+use std::fmt::{self, Display};
+
+use super::{Position, Relation};
+
+/// A row, column or block, the groups of 9 cells which have to hold every digit
+/// once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum House {
+    Row(usize),
+    Column(usize),
+    /// Blocks are numbered left to right, top to bottom
+    Block(usize),
+}
+
+impl House {
+    pub fn new(relation: Relation, index: usize) -> Self {
+        assert!(index < 9, "there are only 9 houses of each kind");
+
+        match relation {
+            Relation::Row => House::Row(index),
+            Relation::Column => House::Column(index),
+            Relation::Block => House::Block(index),
+        }
+    }
+
+    /// All 27 houses, rows first, then columns, then blocks
+    pub fn all() -> impl Iterator<Item = House> {
+        [Relation::Row, Relation::Column, Relation::Block]
+            .iter()
+            .flat_map(|&relation| (0..9).map(move |index| House::new(relation, index)))
+    }
+
+    /// The row, column and block a cell is in
+    pub fn of(position: Position) -> [House; 3] {
+        [
+            House::Row(position.row()),
+            House::Column(position.column()),
+            House::Block(position.block()),
+        ]
+    }
+
+    pub fn relation(self) -> Relation {
+        match self {
+            House::Row(_) => Relation::Row,
+            House::Column(_) => Relation::Column,
+            House::Block(_) => Relation::Block,
+        }
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            House::Row(index) | House::Column(index) | House::Block(index) => index,
+        }
+    }
+
+    /// Where the house is in [`House::all`]
+    pub(super) fn id(self) -> usize {
+        match self {
+            House::Row(index) => index,
+            House::Column(index) => 9 + index,
+            House::Block(index) => 18 + index,
+        }
+    }
+
+    /// The 9 cells of the house, in reading order
+    pub fn cells(self) -> impl Iterator<Item = Position> {
+        (0..9).map(move |i| {
+            match self {
+                House::Row(row) => Position::new(row, i),
+                House::Column(column) => Position::new(i, column),
+                House::Block(block) => Position::in_block(block, i),
+            }
+            .expect("house is not on the board")
+        })
+    }
+
+    pub fn contains(self, position: Position) -> bool {
+        House::of(position).contains(&self)
+    }
+}
+
+/// Counts from 1 like [`Position`](super::Position), so row 0 is `Row 1`
+impl Display for House {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {}", self.relation(), self.index() + 1)
+    }
+}
+
+/// The 20 cells which share a house with `position`, not including `position`
+/// itself
+pub fn peers(position: Position) -> impl Iterator<Item = Position> {
+    let [row, column, block] = House::of(position);
+
+    row.cells()
+        .chain(column.cells())
+        .filter(move |&other| other != position && !block.contains(other))
+        .chain(block.cells().filter(move |&other| other != position))
+}
@@ -0,0 +1,668 @@
+use std::fmt::{self, Display};
+
+use super::{house, Candidates, Digit, House, Move, Position, Relation, SolveStatus, Sudoku};
+
+mod chains;
+mod coloring;
+mod fish;
+mod intersections;
+mod singles;
+mod subsets;
+mod wings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+    HiddenSingle,
+    NakedSingle,
+    Pointing,
+    Claiming,
+    NakedPair,
+    XWing,
+    HiddenPair,
+    NakedTriple,
+    Swordfish,
+    HiddenTriple,
+    XYWing,
+    XYZWing,
+    WWing,
+    FinnedXWing,
+    SashimiXWing,
+    NakedQuad,
+    Jellyfish,
+    FinnedSwordfish,
+    HiddenQuad,
+    SashimiSwordfish,
+    FinnedJellyfish,
+    SashimiJellyfish,
+    SimpleColoring,
+    XCycle,
+    XChain,
+    XYChain,
+    AIC,
+    GroupedAIC,
+}
+
+impl Technique {
+    /// Every technique, in the order the [`LogicalSolver`] tries them: easiest
+    /// first by [`Technique::rating`], so that puzzles are graded by the
+    /// simplest way through them
+    pub const ALL: [Technique; 28] = [
+        Technique::HiddenSingle,
+        Technique::NakedSingle,
+        Technique::Pointing,
+        Technique::Claiming,
+        Technique::NakedPair,
+        Technique::XWing,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::Swordfish,
+        Technique::HiddenTriple,
+        Technique::XYWing,
+        Technique::XYZWing,
+        Technique::WWing,
+        Technique::FinnedXWing,
+        Technique::SashimiXWing,
+        Technique::NakedQuad,
+        Technique::Jellyfish,
+        Technique::FinnedSwordfish,
+        Technique::HiddenQuad,
+        Technique::SashimiSwordfish,
+        Technique::FinnedJellyfish,
+        Technique::SashimiJellyfish,
+        Technique::SimpleColoring,
+        Technique::XCycle,
+        Technique::XChain,
+        Technique::XYChain,
+        Technique::AIC,
+        Technique::GroupedAIC,
+    ];
+
+    fn find(self, solver: &LogicalSolver) -> Option<Deduction> {
+        match self {
+            Technique::NakedSingle => singles::naked_single(solver),
+            Technique::HiddenSingle => singles::hidden_single(solver),
+            Technique::Pointing => intersections::pointing(solver),
+            Technique::Claiming => intersections::claiming(solver),
+            Technique::NakedPair | Technique::NakedTriple | Technique::NakedQuad => {
+                subsets::naked(solver, self)
+            }
+            Technique::HiddenPair | Technique::HiddenTriple | Technique::HiddenQuad => {
+                subsets::hidden(solver, self)
+            }
+            Technique::XWing
+            | Technique::Swordfish
+            | Technique::Jellyfish
+            | Technique::FinnedXWing
+            | Technique::FinnedSwordfish
+            | Technique::FinnedJellyfish
+            | Technique::SashimiXWing
+            | Technique::SashimiSwordfish
+            | Technique::SashimiJellyfish => fish::find(solver, self),
+            Technique::XYWing => wings::xy_wing(solver),
+            Technique::XYZWing => wings::xyz_wing(solver),
+            Technique::WWing => wings::w_wing(solver),
+            Technique::SimpleColoring => coloring::simple_coloring(solver),
+            Technique::XChain => chains::x_chain(solver),
+            Technique::XCycle => chains::x_cycle(solver),
+            Technique::XYChain => chains::xy_chain(solver),
+            Technique::AIC => chains::aic(solver, false),
+            Technique::GroupedAIC => chains::aic(solver, true),
+        }
+    }
+}
+
+impl Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Technique::NakedSingle => "Naked Single",
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::Pointing => "Pointing",
+            Technique::Claiming => "Claiming",
+            Technique::NakedPair => "Naked Pair",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::FinnedXWing => "Finned X-Wing",
+            Technique::FinnedSwordfish => "Finned Swordfish",
+            Technique::FinnedJellyfish => "Finned Jellyfish",
+            Technique::SashimiXWing => "Sashimi X-Wing",
+            Technique::SashimiSwordfish => "Sashimi Swordfish",
+            Technique::SashimiJellyfish => "Sashimi Jellyfish",
+            Technique::XYWing => "XY-Wing",
+            Technique::XYZWing => "XYZ-Wing",
+            Technique::WWing => "W-Wing",
+            Technique::SimpleColoring => "Simple Coloring",
+            Technique::XChain => "X-Chain",
+            Technique::XCycle => "X-Cycle",
+            Technique::XYChain => "XY-Chain",
+            Technique::AIC => "AIC",
+            Technique::GroupedAIC => "Grouped AIC",
+        })
+    }
+}
+
+/// The kind of inference between two neighbouring nodes of a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Link {
+    /// At least one of the two nodes is true
+    Strong,
+    /// At most one of the two nodes is true
+    Weak,
+}
+
+/// One node of a chain: `digit` in one of `cells`, which is a single cell or,
+/// for grouped chains, several cells sharing a block and a line
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChainNode {
+    pub cells: Vec<Position>,
+    pub digit: Digit,
+    /// The link to the next node of the chain, or back to the first node if
+    /// this is the last node of a loop
+    pub link: Option<Link>,
+}
+
+impl Display for ChainNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", u8::from(self.digit))?;
+
+        for (i, cell) in self.cells.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { "" } else { "|" }, cell)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A single logical step, along with everything needed to explain it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deduction {
+    pub technique: Technique,
+    /// The cells the deduction is based on
+    pub cells: Vec<Position>,
+    /// The digits the deduction is based on
+    pub digits: Vec<Digit>,
+    /// The houses the deduction is based on
+    pub base: Vec<House>,
+    /// The houses whose other cells lose candidates, for fish and intersections
+    pub cover: Vec<House>,
+    /// The chain the deduction follows, in order, for chains and coloring
+    pub chain: Vec<ChainNode>,
+    pub placements: Vec<(Position, Digit)>,
+    pub eliminations: Vec<(Position, Digit)>,
+}
+
+impl Deduction {
+    /// The placements and then the eliminations, in move notation
+    pub fn moves(&self) -> Vec<Move> {
+        let placements = self
+            .placements
+            .iter()
+            .map(|&(pos, digit)| Move::Place(pos, digit));
+        let eliminations = self
+            .eliminations
+            .iter()
+            .map(|&(pos, digit)| Move::Eliminate(pos, digit));
+
+        placements.chain(eliminations).collect()
+    }
+}
+
+impl Display for Deduction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.technique)?;
+
+        // Chains list their cells along with the links between them
+        if self.chain.is_empty() {
+            for (i, cell) in self.cells.iter().enumerate() {
+                write!(f, "{}{}", if i == 0 { " " } else { ", " }, cell)?;
+            }
+        }
+
+        for (i, digit) in self.digits.iter().enumerate() {
+            write!(
+                f,
+                "{}{}",
+                if i == 0 { " {" } else { ", " },
+                u8::from(*digit)
+            )?;
+        }
+        if !self.digits.is_empty() {
+            write!(f, "}}")?;
+        }
+
+        for (i, house) in self.base.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " in " } else { ", " }, house)?;
+        }
+
+        for (i, house) in self.cover.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " covered by " } else { ", " }, house)?;
+        }
+
+        for (i, node) in self.chain.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { ": " } else { " " }, node)?;
+
+            match node.link {
+                Some(Link::Strong) => write!(f, " =")?,
+                Some(Link::Weak) => write!(f, " -")?,
+                None => {}
+            }
+        }
+
+        // Loops link back to their first node
+        if let (Some(first), Some(ChainNode { link: Some(_), .. })) =
+            (self.chain.first(), self.chain.last())
+        {
+            write!(f, " {}", first)?;
+        }
+
+        for (i, action) in self.moves().iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " => " } else { ", " }, action)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Solves a board one human style [`Deduction`] at a time
+///
+/// Unlike [`Sudoku::possibilities`], the candidates kept here remember every
+/// elimination made so far
+#[derive(Debug, Clone)]
+pub struct LogicalSolver {
+    sudoku: Sudoku,
+    candidates: Vec<Candidates>,
+}
+
+impl LogicalSolver {
+    pub fn new(sudoku: Sudoku) -> Self {
+        let candidates = Position::all()
+            .map(|pos| match sudoku.get_raw(pos) {
+                Some(_) => Candidates::none(),
+                None => sudoku.possibilities(pos),
+            })
+            .collect();
+
+        LogicalSolver { sudoku, candidates }
+    }
+
+    pub fn sudoku(&self) -> &Sudoku {
+        &self.sudoku
+    }
+
+    pub fn into_sudoku(self) -> Sudoku {
+        self.sudoku
+    }
+
+    pub fn candidates(&self, pos: Position) -> Candidates {
+        self.candidates[pos.index()]
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.sudoku.status() == SolveStatus::Solved
+    }
+
+    /// Finds the next deduction using the simplest technique that makes
+    /// progress, without applying it
+    pub fn next_deduction(&self) -> Option<Deduction> {
+        Technique::ALL
+            .iter()
+            .find_map(|technique| technique.find(self))
+    }
+
+    pub fn apply(&mut self, deduction: &Deduction) {
+        for &(pos, digit) in deduction.placements.iter() {
+            self.sudoku.set(pos, Some(digit));
+            self.candidates[pos.index()].clear();
+
+            for peer in house::peers(pos) {
+                self.candidates[peer.index()].remove(digit);
+            }
+        }
+
+        for &(pos, digit) in deduction.eliminations.iter() {
+            self.candidates[pos.index()].remove(digit);
+        }
+    }
+
+    pub fn step(&mut self) -> Option<Deduction> {
+        let deduction = self.next_deduction()?;
+
+        self.apply(&deduction);
+
+        Some(deduction)
+    }
+
+    /// Applies deductions until the board is solved or no technique makes any
+    /// more progress, returning every deduction made
+    pub fn solve(&mut self) -> Vec<Deduction> {
+        let mut deductions = Vec::new();
+
+        while let Some(deduction) = self.step() {
+            deductions.push(deduction);
+        }
+
+        deductions
+    }
+
+    /// Every empty cell along with its remaining candidates
+    fn unsolved(&self) -> impl Iterator<Item = (Position, Candidates)> + '_ {
+        Position::all()
+            .filter(move |&pos| self.sudoku.get_raw(pos).is_none())
+            .map(move |pos| (pos, self.candidates(pos)))
+    }
+
+    /// The empty cells of `house` which still have `digit` as a candidate
+    fn positions(&self, house: House, digit: Digit) -> Vec<Position> {
+        house
+            .cells()
+            .filter(|&pos| self.candidates(pos).contains(digit))
+            .collect()
+    }
+}
+
+/// All 27 houses, blocks first
+fn houses() -> impl Iterator<Item = House> {
+    [Relation::Block, Relation::Row, Relation::Column]
+        .iter()
+        .flat_map(|&relation| (0..9).map(move |i| House::new(relation, i)))
+}
+
+fn sees(a: Position, b: Position) -> bool {
+    a.row() == b.row() || a.column() == b.column() || a.block() == b.block()
+}
+
+/// Every cell which shares a house with all of `cells`, excluding `cells`
+/// themselves
+fn seen_by_all(cells: &[Position]) -> Vec<Position> {
+    Position::all()
+        .filter(|pos| !cells.contains(pos) && cells.iter().all(|&cell| sees(*pos, cell)))
+        .collect()
+}
+
+/// Every way of picking `size` items from `items`, keeping their order
+fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut picked = Vec::new();
+
+    for (i, &item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[i + 1..], size - 1) {
+            rest.insert(0, item);
+            picked.push(rest);
+        }
+    }
+
+    picked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Puzzles which between them need every family of techniques
+    const PUZZLES: [&str; 6] = [
+        "---6-3----3--1--5---9---2--7--1-6--9-2-----8-1--4-9--3--8---1---5--9--7----7-4---",
+        "--1--4-5--3------6---5-19--9-----4-27------3--139-------84----3-5--2----16---5---",
+        "9-3--7-14------5----84-3-------5--6---9-----15--3-2--------93--2---1-6-8---2----5",
+        "9-3---8-4--2--9----476---------8-9--3--4-6-1--------86---82--6-7-4---2-----93----",
+        "---9-8--54--3--97---6-7---4-----4-5--89--6---7--1--2---7--594-2-9----38----------",
+        "8----91-------16-8---87--4---7-82---28-------3-5------57-2--9-------476-1--9--3--",
+    ];
+
+    /// Solves `puzzle` until `technique` can be used, returning the deduction
+    /// it makes there
+    fn first(puzzle: &str, technique: Technique) -> Deduction {
+        let mut solver = LogicalSolver::new(puzzle.parse().unwrap());
+
+        loop {
+            if let Some(deduction) = technique.find(&solver) {
+                return deduction;
+            }
+
+            solver.step().expect("the technique is used on the way");
+        }
+    }
+
+    /// The first deduction the solver makes with `technique` on its way
+    /// through `puzzle`
+    fn used(puzzle: &str, technique: Technique) -> Deduction {
+        LogicalSolver::new(puzzle.parse().unwrap())
+            .solve()
+            .into_iter()
+            .find(|deduction| deduction.technique == technique)
+            .expect("the technique is used on the way")
+    }
+
+    fn assert_sound(solver: &LogicalSolver, solution: &Sudoku, deduction: &Deduction) {
+        let value = |pos| solution.get_raw(pos).unwrap().value;
+
+        assert!(
+            !deduction.placements.is_empty() || !deduction.eliminations.is_empty(),
+            "{}",
+            deduction
+        );
+
+        for &(pos, digit) in deduction.placements.iter() {
+            assert_eq!(value(pos), digit, "{}", deduction);
+        }
+
+        for &(pos, digit) in deduction.eliminations.iter() {
+            assert_ne!(value(pos), digit, "{}", deduction);
+            assert!(solver.candidates(pos).contains(digit), "{}", deduction);
+        }
+    }
+
+    #[test]
+    fn deductions_agree_with_solution() {
+        for puzzle in PUZZLES.iter() {
+            let sudoku: Sudoku = puzzle.parse().unwrap();
+            let solution = sudoku.solve().unwrap();
+            let mut solver = LogicalSolver::new(sudoku);
+
+            while let Some(deduction) = solver.next_deduction() {
+                assert_sound(&solver, &solution, &deduction);
+
+                // Every technique is tried once the singles run out, as
+                // searching the whole board for chains at every step is slow
+                if deduction.technique > Technique::NakedSingle {
+                    for technique in Technique::ALL.iter() {
+                        if let Some(deduction) = technique.find(&solver) {
+                            assert_sound(&solver, &solution, &deduction);
+                        }
+                    }
+                }
+
+                solver.apply(&deduction);
+            }
+
+            assert!(solver.is_solved(), "{}", puzzle);
+        }
+    }
+
+    fn assert_first(puzzle: &str, technique: Technique, expected: &str) {
+        let deduction = first(puzzle, technique);
+
+        assert_eq!(deduction.technique, technique);
+        assert_eq!(deduction.to_string(), expected);
+    }
+
+    #[test]
+    fn singles_subsets_and_intersections() {
+        let puzzle = PUZZLES[1];
+
+        for &(technique, expected) in [
+            (
+                Technique::HiddenSingle,
+                "Hidden Single r1c7 {3} in Block 3 => r1c7=3",
+            ),
+            (Technique::NakedSingle, "Naked Single r4c2 {8} => r4c2=8"),
+            (
+                Technique::Pointing,
+                "Pointing r1c4, r1c5 {6} in Block 2 covered by Row 1 => r1c1<>6",
+            ),
+            (
+                Technique::Claiming,
+                "Claiming r9c4, r9c5 {3} in Row 9 covered by Block 8 => r8c4<>3, r8c6<>3",
+            ),
+            (
+                Technique::NakedPair,
+                "Naked Pair r1c2, r2c3 {2, 9} in Block 1 => r3c2<>2, r3c3<>2",
+            ),
+            (
+                Technique::HiddenPair,
+                "Hidden Pair r3c1, r6c1 {4, 6} in Column 1 => r3c1<>8",
+            ),
+            (
+                Technique::NakedTriple,
+                "Naked Triple r1c9, r3c9, r9c9 {4, 7, 8} in Column 9 => \
+                 r6c9<>7, r6c9<>8, r8c9<>4, r8c9<>7, r8c9<>8",
+            ),
+            (
+                Technique::HiddenTriple,
+                "Hidden Triple r7c2, r8c3, r9c3 {4, 7, 9} in Block 7 => r7c2<>2, r9c3<>2",
+            ),
+            (
+                Technique::NakedQuad,
+                "Naked Quad r1c9, r3c9, r6c9, r9c9 {4, 5, 7, 8} in Column 9 => \
+                 r8c9<>4, r8c9<>7, r8c9<>8",
+            ),
+            (
+                Technique::HiddenQuad,
+                "Hidden Quad r4c3, r5c2, r5c3, r6c1 {2, 4, 5, 6} in Block 4 => r5c2<>8, r6c1<>8",
+            ),
+        ]
+        .iter()
+        {
+            assert_first(puzzle, technique, expected);
+        }
+    }
+
+    #[test]
+    fn fish_and_wings() {
+        for &(technique, expected) in [
+            (
+                Technique::XWing,
+                "X-Wing r4c3, r4c5, r5c3, r5c5 {5} in Row 4, Row 5 covered by Column 3, Column 5 \
+                 => r2c3<>5, r6c5<>5",
+            ),
+            (
+                Technique::Swordfish,
+                "Swordfish r2c1, r2c3, r4c3, r4c5, r5c3, r5c5 {5} in Row 2, Row 4, Row 5 covered \
+                 by Column 1, Column 3, Column 5 => r6c1<>5, r6c5<>5",
+            ),
+            (
+                Technique::Jellyfish,
+                "Jellyfish r2c1, r6c1, r2c3, r4c3, r5c3, r4c5, r5c5, r6c5, r5c9, r6c9 {5} in \
+                 Column 1, Column 3, Column 5, Column 9 covered by Row 2, Row 4, Row 5, Row 6 => \
+                 r5c7<>5, r6c7<>5",
+            ),
+            (
+                Technique::FinnedXWing,
+                "Finned X-Wing r4c4, r5c4, r8c4, r5c9, r8c9 {1} in Column 4, Column 9 covered by \
+                 Row 5, Row 8 => r5c5<>1",
+            ),
+            (
+                Technique::SashimiXWing,
+                "Sashimi X-Wing r2c1, r6c1, r5c9, r6c9 {5} in Column 1, Column 9 covered by Row \
+                 2, Row 6 => r6c7<>5",
+            ),
+            (
+                Technique::FinnedSwordfish,
+                "Finned Swordfish r2c7, r2c8, r4c4, r4c5, r4c8, r7c5, r7c7, r7c8 {1} in Row 2, \
+                 Row 4, Row 7 covered by Column 5, Column 7, Column 8 => r5c5<>1",
+            ),
+            (
+                Technique::SashimiSwordfish,
+                "Sashimi Swordfish r2c1, r3c1, r6c1, r3c2, r5c2, r5c5, r6c5 {4} in Column 1, \
+                 Column 2, Column 5 covered by Row 2, Row 5, Row 6 => r2c3<>4",
+            ),
+            (
+                Technique::FinnedJellyfish,
+                "Finned Jellyfish r2c7, r2c8, r4c4, r4c5, r4c8, r5c4, r5c5, r5c7, r8c4, r8c7, \
+                 r8c8, r8c9 {1} in Row 2, Row 4, Row 5, Row 8 covered by Column 4, Column 5, \
+                 Column 7, Column 8 => r7c7<>1, r7c8<>1",
+            ),
+            (
+                Technique::SashimiJellyfish,
+                "Sashimi Jellyfish r2c3, r2c8, r6c1, r6c5, r8c3, r8c8, r8c9, r9c3, r9c8, r9c9 {4} \
+                 in Row 2, Row 6, Row 8, Row 9 covered by Column 3, Column 5, Column 8, Column 9 \
+                 => r5c3<>4",
+            ),
+            (
+                Technique::XYWing,
+                "XY-Wing r5c6, r4c4, r2c6 {6, 8, 7} => r2c4<>7",
+            ),
+            (
+                Technique::WWing,
+                "W-Wing r2c6, r9c5, r5c6, r6c5 {8, 7} in Block 5 => r2c5<>7, r7c6<>7, r8c6<>7",
+            ),
+        ]
+        .iter()
+        {
+            assert_first(PUZZLES[1], technique, expected);
+        }
+
+        assert_first(
+            PUZZLES[0],
+            Technique::XYZWing,
+            "XYZ-Wing r5c5, r5c4, r5c6 {3, 5, 7} => r4c5<>5, r5c1<>5, r5c3<>5, r5c7<>5, r5c9<>5, \
+             r6c5<>5",
+        );
+    }
+
+    #[test]
+    fn coloring_and_chains() {
+        for &(technique, expected) in [
+            (
+                Technique::SimpleColoring,
+                "Simple Coloring {3}: 3r9c4 = 3r9c5 => r8c4<>3, r8c6<>3",
+            ),
+            (
+                Technique::XCycle,
+                "X-Cycle {1}: 1r2c7 = 1r2c8 - 1r4c8 = 1r5c7 - 1r2c7 => r7c7<>1, r7c8<>1, r8c7<>1, \
+                 r8c8<>1",
+            ),
+            (
+                Technique::XChain,
+                "X-Chain {1}: 1r5c9 = 1r8c9 - 1r8c4 = 1r7c5 => r5c5<>1",
+            ),
+            (
+                Technique::XYChain,
+                "XY-Chain {2, 4, 6, 9}: 6r1c5 = 9r1c5 - 9r1c2 = 2r1c2 - 2r5c2 = 4r5c2 - 4r6c1 = \
+                 6r6c1 => r6c5<>6",
+            ),
+        ]
+        .iter()
+        {
+            assert_first(PUZZLES[1], technique, expected);
+        }
+
+        // The first of these found by the searches are single strong links, so
+        // check longer chains the solver actually needs
+        for &(puzzle, technique, expected) in [
+            (
+                PUZZLES[4],
+                Technique::AIC,
+                "AIC {1, 4, 5}: 1r1c2 = 1r1c1 - 1r5c1 = 5r5c1 - 5r6c3 = 4r6c3 - 4r6c2 = 4r9c2 => \
+                 r9c2<>1",
+            ),
+            (
+                PUZZLES[5],
+                Technique::GroupedAIC,
+                "Grouped AIC {5, 6, 9}: 9r5c5 = 5r5c5 - 5r9c5 = 6r9c5 - 6r9c2 = 6r4c2 - 9r4c2 = \
+                 9r4c8|r4c9 => r5c8<>9",
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(used(puzzle, technique).to_string(), expected);
+        }
+    }
+}
@@ -0,0 +1,445 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use petgraph::{graph::NodeIndex, visit::EdgeRef, Graph, Undirected};
+
+use super::{
+    houses, seen_by_all, sees, ChainNode, Deduction, Digit, House, Link, LogicalSolver, Position,
+    Technique,
+};
+
+/// A candidate, or for grouped chains a digit in any of several cells of the
+/// same block and line
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Node {
+    cells: Vec<Position>,
+    digit: Digit,
+}
+
+/// Which inferences the link graph is built from
+#[derive(Debug, Clone, Copy)]
+struct Rules {
+    /// Only build links for this digit
+    digit: Option<Digit>,
+    /// Strong links between the only two places for a digit in a house
+    strong_in_house: bool,
+    /// Strong links between the two candidates of a cell
+    strong_in_cell: bool,
+    /// Weak links between any two candidates of a cell
+    weak_in_cell: bool,
+    /// Group nodes spanning a block and line intersection
+    grouped: bool,
+}
+
+type Links = Graph<Node, Link, Undirected>;
+
+type Eliminations = Vec<(Position, Digit)>;
+
+fn links(solver: &LogicalSolver, rules: Rules) -> Links {
+    let mut graph = Graph::new_undirected();
+    let mut nodes = HashMap::new();
+
+    let digits = || Digit::iterator().filter(|&digit| rules.digit.map_or(true, |d| d == digit));
+
+    for (pos, candidates) in solver.unsolved() {
+        for digit in digits().filter(|&digit| candidates.contains(digit)) {
+            let node = Node {
+                cells: vec![pos],
+                digit,
+            };
+
+            nodes.insert(node.clone(), graph.add_node(node));
+        }
+    }
+
+    if rules.grouped {
+        for block in 0..9 {
+            for digit in digits() {
+                let positions = solver.positions(House::Block(block), digit);
+
+                let rows = positions.iter().map(|pos| House::Row(pos.row()));
+                let columns = positions.iter().map(|pos| House::Column(pos.column()));
+
+                for line in rows.chain(columns).collect::<BTreeSet<_>>() {
+                    let cells: Vec<_> = positions
+                        .iter()
+                        .copied()
+                        .filter(|&pos| line.contains(pos))
+                        .collect();
+
+                    if cells.len() >= 2 {
+                        let node = Node { cells, digit };
+
+                        nodes.insert(node.clone(), graph.add_node(node));
+                    }
+                }
+            }
+        }
+    }
+
+    if rules.strong_in_house {
+        for house in houses() {
+            for digit in digits() {
+                let positions: BTreeSet<_> = solver.positions(house, digit).into_iter().collect();
+                let house_nodes: Vec<_> = graph
+                    .node_indices()
+                    .filter(|&node| {
+                        graph[node].digit == digit
+                            && graph[node].cells.iter().all(|pos| positions.contains(pos))
+                    })
+                    .collect();
+
+                for &a in house_nodes.iter() {
+                    for &b in house_nodes.iter().filter(|&&b| a < b) {
+                        let union: BTreeSet<_> = graph[a]
+                            .cells
+                            .iter()
+                            .chain(graph[b].cells.iter())
+                            .copied()
+                            .collect();
+
+                        if disjoint(&graph[a], &graph[b]) && union == positions {
+                            graph.update_edge(a, b, Link::Strong);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if rules.strong_in_cell || rules.weak_in_cell {
+        for (pos, candidates) in solver.unsolved() {
+            let cell_nodes: Vec<_> = digits()
+                .filter_map(|digit| {
+                    nodes
+                        .get(&Node {
+                            cells: vec![pos],
+                            digit,
+                        })
+                        .copied()
+                })
+                .collect();
+
+            for &a in cell_nodes.iter() {
+                for &b in cell_nodes.iter().filter(|&&b| a < b) {
+                    if rules.strong_in_cell && candidates.len() == 2 {
+                        graph.update_edge(a, b, Link::Strong);
+                    } else if rules.weak_in_cell {
+                        graph.update_edge(a, b, Link::Weak);
+                    }
+                }
+            }
+        }
+    }
+
+    let indices: Vec<_> = graph.node_indices().collect();
+    for &a in indices.iter() {
+        for &b in indices.iter().filter(|&&b| a < b) {
+            if graph[a].digit == graph[b].digit
+                && disjoint(&graph[a], &graph[b])
+                && graph[a]
+                    .cells
+                    .iter()
+                    .all(|&x| graph[b].cells.iter().all(|&y| sees(x, y)))
+                && graph.find_edge(a, b).is_none()
+            {
+                graph.add_edge(a, b, Link::Weak);
+            }
+        }
+    }
+
+    graph
+}
+
+fn disjoint(a: &Node, b: &Node) -> bool {
+    a.cells.iter().all(|pos| !b.cells.contains(pos))
+}
+
+/// The longest chain, in nodes, that is searched for
+const MAX_LENGTH: usize = 16;
+
+/// Finds the shortest alternating chain, starting and ending with a strong
+/// link, for which `conclude` finds any eliminations
+///
+/// Such a chain means that either the first or the last node is true.
+/// `conclude` is given the nodes of the chain and whether the last node also
+/// sees the first node, closing a loop
+fn search<F>(graph: &Links, mut conclude: F) -> Option<(Vec<NodeIndex>, Eliminations)>
+where
+    F: FnMut(&[NodeIndex], bool) -> Eliminations,
+{
+    let mut best: Option<(Vec<NodeIndex>, Eliminations)> = None;
+
+    for start in graph.node_indices() {
+        // A state is a node and the link used to reach it, the next link has
+        // to be of the other kind
+        let mut parents = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        parents.insert((start, Link::Weak), None);
+        queue.push_back((start, Link::Weak, 1));
+
+        while let Some((node, reached_by, length)) = queue.pop_front() {
+            if length >= MAX_LENGTH
+                || best
+                    .as_ref()
+                    .map_or(false, |(chain, _)| length + 1 >= chain.len())
+            {
+                break;
+            }
+
+            let next_link = match reached_by {
+                Link::Weak => Link::Strong,
+                Link::Strong => Link::Weak,
+            };
+
+            for edge in graph.edges(node) {
+                // Strong links also work as weak links
+                if next_link == Link::Strong && *edge.weight() != Link::Strong {
+                    continue;
+                }
+
+                let next = edge.target();
+                if parents.contains_key(&(next, next_link)) {
+                    continue;
+                }
+
+                parents.insert((next, next_link), Some((node, reached_by)));
+                queue.push_back((next, next_link, length + 1));
+
+                if next_link == Link::Weak {
+                    continue;
+                }
+
+                let mut chain = vec![next];
+                let mut state = (node, reached_by);
+                while let Some(&parent) = parents.get(&state) {
+                    chain.push(state.0);
+                    match parent {
+                        Some(parent) => state = parent,
+                        None => break,
+                    }
+                }
+                chain.reverse();
+
+                let unique: BTreeSet<_> = chain.iter().collect();
+                if unique.len() != chain.len() {
+                    continue;
+                }
+
+                let is_loop = chain.len() >= 4 && graph.find_edge(next, start).is_some();
+                let eliminations = conclude(&chain, is_loop);
+
+                if !eliminations.is_empty() {
+                    best = Some((chain, eliminations));
+                    break;
+                }
+            }
+        }
+    }
+
+    best
+}
+
+/// Either the first or the last node of a chain is true, so anything which
+/// conflicts with both of them is false
+fn endpoint_eliminations(solver: &LogicalSolver, first: &Node, last: &Node) -> Eliminations {
+    if first.digit == last.digit {
+        let ends: Vec<_> = first
+            .cells
+            .iter()
+            .chain(last.cells.iter())
+            .copied()
+            .collect();
+
+        return seen_by_all(&ends)
+            .into_iter()
+            .filter(|&pos| solver.candidates(pos).contains(first.digit))
+            .map(|pos| (pos, first.digit))
+            .collect();
+    }
+
+    match (&first.cells[..], &last.cells[..]) {
+        ([a], [b]) if a == b => solver
+            .candidates(*a)
+            .iter()
+            .filter(|&digit| digit != first.digit && digit != last.digit)
+            .map(|digit| (*a, digit))
+            .collect(),
+        ([a], [b]) if sees(*a, *b) => [(*b, first.digit), (*a, last.digit)]
+            .iter()
+            .copied()
+            .filter(|&(pos, digit)| solver.candidates(pos).contains(digit))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// In a continuous loop every weak link is also strong, so anything which
+/// conflicts with both ends of a weak link is false
+fn loop_eliminations(solver: &LogicalSolver, graph: &Links, chain: &[NodeIndex]) -> Eliminations {
+    let mut eliminations = BTreeSet::new();
+
+    // Links alternate starting with a strong one, and the closing link is weak
+    for i in (1..chain.len()).step_by(2) {
+        let a = &graph[chain[i]];
+        let b = &graph[chain[(i + 1) % chain.len()]];
+
+        if a.digit == b.digit {
+            let ends: Vec<_> = a.cells.iter().chain(b.cells.iter()).copied().collect();
+
+            eliminations.extend(
+                seen_by_all(&ends)
+                    .into_iter()
+                    .filter(|&pos| solver.candidates(pos).contains(a.digit))
+                    .filter(|pos| {
+                        !chain.iter().any(|&node| {
+                            graph[node].digit == a.digit && graph[node].cells.contains(pos)
+                        })
+                    })
+                    .map(|pos| (pos, a.digit)),
+            );
+        } else if let ([pos], true) = (&a.cells[..], a.cells == b.cells) {
+            eliminations.extend(
+                solver
+                    .candidates(*pos)
+                    .iter()
+                    .filter(|&digit| digit != a.digit && digit != b.digit)
+                    .map(|digit| (*pos, digit)),
+            );
+        }
+    }
+
+    eliminations.into_iter().collect()
+}
+
+fn deduction(
+    technique: Technique,
+    graph: &Links,
+    chain: &[NodeIndex],
+    is_loop: bool,
+    eliminations: Eliminations,
+) -> Deduction {
+    let mut cells = Vec::new();
+    for node in chain {
+        for &pos in graph[*node].cells.iter() {
+            if !cells.contains(&pos) {
+                cells.push(pos);
+            }
+        }
+    }
+
+    let digits: BTreeSet<_> = chain.iter().map(|&node| graph[node].digit).collect();
+
+    Deduction {
+        technique,
+        cells,
+        digits: digits.into_iter().collect(),
+        base: Vec::new(),
+        cover: Vec::new(),
+        chain: chain
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| ChainNode {
+                cells: graph[node].cells.clone(),
+                digit: graph[node].digit,
+                link: if i + 1 < chain.len() {
+                    Some(if i % 2 == 0 { Link::Strong } else { Link::Weak })
+                } else if is_loop {
+                    Some(Link::Weak)
+                } else {
+                    None
+                },
+            })
+            .collect(),
+        placements: Vec::new(),
+        eliminations,
+    }
+}
+
+fn single_digit_rules(digit: Digit) -> Rules {
+    Rules {
+        digit: Some(digit),
+        strong_in_house: true,
+        strong_in_cell: false,
+        weak_in_cell: false,
+        grouped: false,
+    }
+}
+
+/// A chain of conjugate pairs of a single digit, cells seeing both ends can not
+/// hold the digit
+pub(super) fn x_chain(solver: &LogicalSolver) -> Option<Deduction> {
+    Digit::iterator().find_map(|digit| {
+        let graph = links(solver, single_digit_rules(digit));
+
+        search(&graph, |chain, _| {
+            endpoint_eliminations(solver, &graph[chain[0]], &graph[chain[chain.len() - 1]])
+        })
+        .map(|(chain, eliminations)| {
+            deduction(Technique::XChain, &graph, &chain, false, eliminations)
+        })
+    })
+}
+
+/// A continuous loop of alternating links on a single digit
+pub(super) fn x_cycle(solver: &LogicalSolver) -> Option<Deduction> {
+    Digit::iterator().find_map(|digit| {
+        let graph = links(solver, single_digit_rules(digit));
+
+        search(&graph, |chain, is_loop| {
+            if is_loop {
+                loop_eliminations(solver, &graph, chain)
+            } else {
+                Vec::new()
+            }
+        })
+        .map(|(chain, eliminations)| {
+            deduction(Technique::XCycle, &graph, &chain, true, eliminations)
+        })
+    })
+}
+
+/// A chain of cells with two candidates each, linked by a shared digit
+pub(super) fn xy_chain(solver: &LogicalSolver) -> Option<Deduction> {
+    let graph = links(
+        solver,
+        Rules {
+            digit: None,
+            strong_in_house: false,
+            strong_in_cell: true,
+            weak_in_cell: false,
+            grouped: false,
+        },
+    );
+
+    search(&graph, |chain, _| {
+        endpoint_eliminations(solver, &graph[chain[0]], &graph[chain[chain.len() - 1]])
+    })
+    .map(|(chain, eliminations)| deduction(Technique::XYChain, &graph, &chain, false, eliminations))
+}
+
+/// An alternating inference chain mixing links within houses and cells,
+/// optionally with group nodes
+pub(super) fn aic(solver: &LogicalSolver, grouped: bool) -> Option<Deduction> {
+    let graph = links(
+        solver,
+        Rules {
+            digit: None,
+            strong_in_house: true,
+            strong_in_cell: true,
+            weak_in_cell: true,
+            grouped,
+        },
+    );
+
+    let technique = if grouped {
+        Technique::GroupedAIC
+    } else {
+        Technique::AIC
+    };
+
+    search(&graph, |chain, _| {
+        endpoint_eliminations(solver, &graph[chain[0]], &graph[chain[chain.len() - 1]])
+    })
+    .map(|(chain, eliminations)| deduction(technique, &graph, &chain, false, eliminations))
+}
@@ -0,0 +1,155 @@
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+use petgraph::{algo::astar, graph::NodeIndex, Graph, Undirected};
+
+use super::{houses, sees, ChainNode, Deduction, Digit, Link, LogicalSolver, Position, Technique};
+
+/// Every conjugate pair of `digit`, that is every pair of cells which are the
+/// only two places left for it in some house
+fn conjugate_pairs(solver: &LogicalSolver, digit: Digit) -> Graph<Position, (), Undirected> {
+    let mut graph = Graph::new_undirected();
+    let mut nodes = HashMap::new();
+
+    for house in houses() {
+        if let [a, b] = solver.positions(house, digit)[..] {
+            let a = *nodes.entry(a).or_insert_with(|| graph.add_node(a));
+            let b = *nodes.entry(b).or_insert_with(|| graph.add_node(b));
+
+            graph.update_edge(a, b, ());
+        }
+    }
+
+    graph
+}
+
+/// Colors each cluster of conjugate pairs with two alternating colors, one of
+/// which has to be the true one. Returns the cluster and color of every node.
+fn color(graph: &Graph<Position, (), Undirected>) -> HashMap<NodeIndex, (NodeIndex, bool)> {
+    let mut colors = HashMap::new();
+
+    for root in graph.node_indices() {
+        if colors.contains_key(&root) {
+            continue;
+        }
+
+        let mut queue = VecDeque::new();
+        colors.insert(root, (root, true));
+        queue.push_back(root);
+
+        while let Some(node) = queue.pop_front() {
+            let color = colors[&node].1;
+
+            for neighbor in graph.neighbors(node) {
+                if let Entry::Vacant(entry) = colors.entry(neighbor) {
+                    entry.insert((root, !color));
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    colors
+}
+
+/// Colors the conjugate pairs of a digit. If two cells of the same color see
+/// each other that color is false (a color wrap), otherwise any cell seeing
+/// both colors can not hold the digit (a color trap).
+pub(super) fn simple_coloring(solver: &LogicalSolver) -> Option<Deduction> {
+    Digit::iterator().find_map(|digit| {
+        let graph = conjugate_pairs(solver, digit);
+        let colors = color(&graph);
+
+        let pairs: Vec<_> = graph
+            .node_indices()
+            .flat_map(|a| graph.node_indices().map(move |b| (a, b)))
+            .filter(|(a, b)| a < b && colors[a].0 == colors[b].0)
+            .collect();
+
+        let wrap = pairs.iter().find_map(|&(a, b)| {
+            if colors[&a].1 != colors[&b].1 || !sees(graph[a], graph[b]) {
+                return None;
+            }
+
+            let eliminations = graph
+                .node_indices()
+                .filter(|node| colors[node] == colors[&a])
+                .map(|node| (graph[node], digit))
+                .collect();
+
+            Some(deduction(
+                &graph,
+                digit,
+                a,
+                b,
+                Some(Link::Weak),
+                eliminations,
+            ))
+        });
+
+        wrap.or_else(|| {
+            pairs.iter().find_map(|&(a, b)| {
+                if colors[&a].1 == colors[&b].1 {
+                    return None;
+                }
+
+                let cluster = colors[&a].0;
+                let eliminations: Vec<_> = solver
+                    .unsolved()
+                    .map(|(pos, _)| pos)
+                    .filter(|&pos| {
+                        solver.candidates(pos).contains(digit)
+                            && sees(pos, graph[a])
+                            && sees(pos, graph[b])
+                            && !graph
+                                .node_indices()
+                                .any(|node| graph[node] == pos && colors[&node].0 == cluster)
+                    })
+                    .map(|pos| (pos, digit))
+                    .collect();
+
+                if eliminations.is_empty() {
+                    return None;
+                }
+
+                Some(deduction(&graph, digit, a, b, None, eliminations))
+            })
+        })
+    })
+}
+
+fn deduction(
+    graph: &Graph<Position, (), Undirected>,
+    digit: Digit,
+    from: NodeIndex,
+    to: NodeIndex,
+    closing_link: Option<Link>,
+    eliminations: Vec<(Position, Digit)>,
+) -> Deduction {
+    let (_, path) = astar(graph, from, |node| node == to, |_| 1, |_| 0)
+        .expect("the cells of a cluster are connected");
+
+    let chain = path
+        .iter()
+        .enumerate()
+        .map(|(i, &node)| ChainNode {
+            cells: vec![graph[node]],
+            digit,
+            link: if i + 1 == path.len() {
+                closing_link
+            } else {
+                Some(Link::Strong)
+            },
+        })
+        .collect();
+
+    Deduction {
+        technique: Technique::SimpleColoring,
+        cells: path.iter().map(|&node| graph[node]).collect(),
+        digits: vec![digit],
+        base: Vec::new(),
+        cover: Vec::new(),
+        chain,
+        placements: Vec::new(),
+        eliminations,
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeSet;
+
+use super::{combinations, Deduction, Digit, House, LogicalSolver, Position, Relation, Technique};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Basic,
+    Finned,
+    Sashimi,
+}
+
+fn shape_of(technique: Technique) -> (usize, Kind) {
+    match technique {
+        Technique::XWing => (2, Kind::Basic),
+        Technique::Swordfish => (3, Kind::Basic),
+        Technique::Jellyfish => (4, Kind::Basic),
+        Technique::FinnedXWing => (2, Kind::Finned),
+        Technique::FinnedSwordfish => (3, Kind::Finned),
+        Technique::FinnedJellyfish => (4, Kind::Finned),
+        Technique::SashimiXWing => (2, Kind::Sashimi),
+        Technique::SashimiSwordfish => (3, Kind::Sashimi),
+        Technique::SashimiJellyfish => (4, Kind::Sashimi),
+        _ => unreachable!("{} is not a fish", technique),
+    }
+}
+
+/// The index of the line of the given kind which passes through `pos`
+fn line_of(relation: Relation, pos: Position) -> usize {
+    match relation {
+        Relation::Row => pos.row(),
+        Relation::Column => pos.column(),
+        Relation::Block => pos.block(),
+    }
+}
+
+/// `n` base lines whose candidates for a digit all lie in `n` cover lines, so
+/// the digit can be removed from the rest of the cover lines
+///
+/// Finned fish also allow extra candidates (the fins) in the base lines as
+/// long as they all share a block, in which case only cells of the cover lines
+/// in that block lose the digit. A finned fish is sashimi if it would be
+/// degenerate without its fins.
+pub(super) fn find(solver: &LogicalSolver, technique: Technique) -> Option<Deduction> {
+    let (size, kind) = shape_of(technique);
+    let max_positions = match kind {
+        Kind::Basic => size,
+        Kind::Finned | Kind::Sashimi => size + 3,
+    };
+
+    [
+        (Relation::Row, Relation::Column),
+        (Relation::Column, Relation::Row),
+    ]
+    .iter()
+    .find_map(|&(base_relation, cover_relation)| {
+        Digit::iterator().find_map(|digit| {
+            let lines: Vec<_> = (0..9)
+                .filter(|&i| {
+                    (2..=max_positions)
+                        .contains(&solver.positions(House::new(base_relation, i), digit).len())
+                })
+                .collect();
+
+            combinations(&lines, size).into_iter().find_map(|base| {
+                let cells: Vec<_> = base
+                    .iter()
+                    .flat_map(|&i| solver.positions(House::new(base_relation, i), digit))
+                    .collect();
+                let covers: Vec<_> = cells
+                    .iter()
+                    .map(|&pos| line_of(cover_relation, pos))
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect();
+
+                let fish = Fish {
+                    technique,
+                    digit,
+                    base_relation,
+                    cover_relation,
+                    base: &base,
+                    cells: &cells,
+                };
+
+                match kind {
+                    Kind::Basic if covers.len() == size => fish.eliminate(solver, &covers, None),
+                    Kind::Finned | Kind::Sashimi if covers.len() > size => {
+                        combinations(&covers, size)
+                            .into_iter()
+                            .find_map(|cover| fish.finned(solver, &cover, kind))
+                    }
+                    _ => None,
+                }
+            })
+        })
+    })
+}
+
+struct Fish<'a> {
+    technique: Technique,
+    digit: Digit,
+    base_relation: Relation,
+    cover_relation: Relation,
+    base: &'a [usize],
+    cells: &'a [Position],
+}
+
+impl Fish<'_> {
+    fn finned(&self, solver: &LogicalSolver, cover: &[usize], kind: Kind) -> Option<Deduction> {
+        let fins: Vec<_> = self
+            .cells
+            .iter()
+            .copied()
+            .filter(|&pos| !cover.contains(&line_of(self.cover_relation, pos)))
+            .collect();
+
+        let fin_block = fins.first()?.block();
+        if fins.iter().any(|pos| pos.block() != fin_block) {
+            return None;
+        }
+
+        let body_sizes: Vec<_> = self
+            .base
+            .iter()
+            .map(|&line| {
+                self.cells
+                    .iter()
+                    .filter(|&&pos| {
+                        line_of(self.base_relation, pos) == line && !fins.contains(&pos)
+                    })
+                    .count()
+            })
+            .collect();
+
+        if body_sizes.contains(&0) {
+            return None;
+        }
+
+        let is_sashimi = body_sizes.iter().any(|&size| size < 2);
+        if is_sashimi != (kind == Kind::Sashimi) {
+            return None;
+        }
+
+        self.eliminate(solver, cover, Some(fin_block))
+    }
+
+    fn eliminate(
+        &self,
+        solver: &LogicalSolver,
+        cover: &[usize],
+        fin_block: Option<usize>,
+    ) -> Option<Deduction> {
+        let eliminations: Vec<_> = cover
+            .iter()
+            .flat_map(|&line| solver.positions(House::new(self.cover_relation, line), self.digit))
+            .filter(|&pos| !self.base.contains(&line_of(self.base_relation, pos)))
+            .filter(|pos| fin_block.map_or(true, |block| pos.block() == block))
+            .map(|pos| (pos, self.digit))
+            .collect();
+
+        if eliminations.is_empty() {
+            return None;
+        }
+
+        Some(Deduction {
+            technique: self.technique,
+            cells: self.cells.to_vec(),
+            digits: vec![self.digit],
+            base: self
+                .base
+                .iter()
+                .map(|&line| House::new(self.base_relation, line))
+                .collect(),
+            cover: cover
+                .iter()
+                .map(|&line| House::new(self.cover_relation, line))
+                .collect(),
+            chain: Vec::new(),
+            placements: Vec::new(),
+            eliminations,
+        })
+    }
+}
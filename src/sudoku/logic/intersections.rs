@@ -0,0 +1,83 @@
+use super::{Deduction, Digit, House, LogicalSolver, Technique};
+
+/// A digit which, within a block, only fits in one row or column, so it can be
+/// removed from the rest of that line
+pub(super) fn pointing(solver: &LogicalSolver) -> Option<Deduction> {
+    (0..9).find_map(|block| {
+        Digit::iterator().find_map(|digit| {
+            let cells = solver.positions(House::Block(block), digit);
+
+            let line = if cells.len() < 2 {
+                return None;
+            } else if cells.iter().all(|pos| pos.row() == cells[0].row()) {
+                House::Row(cells[0].row())
+            } else if cells.iter().all(|pos| pos.column() == cells[0].column()) {
+                House::Column(cells[0].column())
+            } else {
+                return None;
+            };
+
+            let eliminations: Vec<_> = solver
+                .positions(line, digit)
+                .into_iter()
+                .filter(|pos| pos.block() != block)
+                .map(|pos| (pos, digit))
+                .collect();
+
+            if eliminations.is_empty() {
+                return None;
+            }
+
+            Some(Deduction {
+                technique: Technique::Pointing,
+                cells,
+                digits: vec![digit],
+                base: vec![House::Block(block)],
+                cover: vec![line],
+                chain: Vec::new(),
+                placements: Vec::new(),
+                eliminations,
+            })
+        })
+    })
+}
+
+/// A digit which, within a row or column, only fits in one block, so it can be
+/// removed from the rest of that block
+pub(super) fn claiming(solver: &LogicalSolver) -> Option<Deduction> {
+    (0..9)
+        .map(House::Row)
+        .chain((0..9).map(House::Column))
+        .find_map(|line| {
+            Digit::iterator().find_map(|digit| {
+                let cells = solver.positions(line, digit);
+
+                if cells.len() < 2 || cells.iter().any(|pos| pos.block() != cells[0].block()) {
+                    return None;
+                }
+
+                let block = House::Block(cells[0].block());
+                let eliminations: Vec<_> = solver
+                    .positions(block, digit)
+                    .into_iter()
+                    .filter(|&pos| !line.contains(pos))
+                    .map(|pos| (pos, digit))
+                    .collect();
+
+                if eliminations.is_empty() {
+                    return None;
+                }
+
+                Some(Deduction {
+                    technique: Technique::Claiming,
+                    cells,
+                    digits: vec![digit],
+                    base: vec![line],
+                    cover: vec![block],
+                    chain: Vec::new(),
+                    placements: Vec::new(),
+                    eliminations,
+                })
+            })
+        })
+}
@@ -0,0 +1,42 @@
+use super::{houses, Deduction, Digit, LogicalSolver, Technique};
+
+/// A cell with only one candidate left
+pub(super) fn naked_single(solver: &LogicalSolver) -> Option<Deduction> {
+    solver.unsolved().find_map(|(pos, candidates)| {
+        if candidates.len() != 1 {
+            return None;
+        }
+
+        let digit = candidates.first()?;
+
+        Some(Deduction {
+            technique: Technique::NakedSingle,
+            cells: vec![pos],
+            digits: vec![digit],
+            base: Vec::new(),
+            cover: Vec::new(),
+            chain: Vec::new(),
+            placements: vec![(pos, digit)],
+            eliminations: Vec::new(),
+        })
+    })
+}
+
+/// A digit which only fits in one cell of a house
+pub(super) fn hidden_single(solver: &LogicalSolver) -> Option<Deduction> {
+    houses().find_map(|house| {
+        Digit::iterator().find_map(|digit| match solver.positions(house, digit)[..] {
+            [pos] => Some(Deduction {
+                technique: Technique::HiddenSingle,
+                cells: vec![pos],
+                digits: vec![digit],
+                base: vec![house],
+                cover: Vec::new(),
+                chain: Vec::new(),
+                placements: vec![(pos, digit)],
+                eliminations: Vec::new(),
+            }),
+            _ => None,
+        })
+    })
+}
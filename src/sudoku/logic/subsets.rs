@@ -0,0 +1,120 @@
+use std::collections::BTreeSet;
+
+use super::{combinations, houses, Candidates, Deduction, Digit, LogicalSolver, Technique};
+
+fn size_of(technique: Technique) -> usize {
+    match technique {
+        Technique::NakedPair | Technique::HiddenPair => 2,
+        Technique::NakedTriple | Technique::HiddenTriple => 3,
+        Technique::NakedQuad | Technique::HiddenQuad => 4,
+        _ => unreachable!("{} is not a subset technique", technique),
+    }
+}
+
+/// `n` cells of a house which only have `n` candidates between them, so those
+/// digits can not go anywhere else in the house
+pub(super) fn naked(solver: &LogicalSolver, technique: Technique) -> Option<Deduction> {
+    let size = size_of(technique);
+
+    houses().find_map(|house| {
+        let unsolved: Vec<_> = house
+            .cells()
+            .filter(|&pos| solver.sudoku.get_raw(pos).is_none())
+            .collect();
+
+        let subset_cells: Vec<_> = unsolved
+            .iter()
+            .copied()
+            .filter(|&pos| (2..=size).contains(&solver.candidates(pos).len()))
+            .collect();
+
+        combinations(&subset_cells, size)
+            .into_iter()
+            .find_map(|cells| {
+                let digits: Candidates = cells
+                    .iter()
+                    .flat_map(|&pos| solver.candidates(pos))
+                    .collect();
+
+                if digits.len() != size {
+                    return None;
+                }
+
+                let eliminations: Vec<_> = unsolved
+                    .iter()
+                    .filter(|pos| !cells.contains(pos))
+                    .flat_map(|&pos| {
+                        (solver.candidates(pos) & digits)
+                            .iter()
+                            .map(move |digit| (pos, digit))
+                    })
+                    .collect();
+
+                if eliminations.is_empty() {
+                    return None;
+                }
+
+                Some(Deduction {
+                    technique,
+                    cells,
+                    digits: digits.into_iter().collect(),
+                    base: vec![house],
+                    cover: Vec::new(),
+                    chain: Vec::new(),
+                    placements: Vec::new(),
+                    eliminations,
+                })
+            })
+    })
+}
+
+/// `n` digits which only fit in `n` cells of a house, so those cells can not
+/// hold any other digit
+pub(super) fn hidden(solver: &LogicalSolver, technique: Technique) -> Option<Deduction> {
+    let size = size_of(technique);
+
+    houses().find_map(|house| {
+        let subset_digits: Vec<_> = Digit::iterator()
+            .filter(|&digit| (2..=size).contains(&solver.positions(house, digit).len()))
+            .collect();
+
+        combinations(&subset_digits, size)
+            .into_iter()
+            .find_map(|digits| {
+                let cells: BTreeSet<_> = digits
+                    .iter()
+                    .flat_map(|&digit| solver.positions(house, digit))
+                    .collect();
+
+                if cells.len() != size {
+                    return None;
+                }
+
+                let eliminations: Vec<_> = cells
+                    .iter()
+                    .flat_map(|&pos| {
+                        solver
+                            .candidates(pos)
+                            .iter()
+                            .filter(|digit| !digits.contains(digit))
+                            .map(move |digit| (pos, digit))
+                    })
+                    .collect();
+
+                if eliminations.is_empty() {
+                    return None;
+                }
+
+                Some(Deduction {
+                    technique,
+                    cells: cells.into_iter().collect(),
+                    digits,
+                    base: vec![house],
+                    cover: Vec::new(),
+                    chain: Vec::new(),
+                    placements: Vec::new(),
+                    eliminations,
+                })
+            })
+    })
+}
@@ -0,0 +1,158 @@
+use super::{houses, seen_by_all, sees, Deduction, Digit, LogicalSolver, Position, Technique};
+
+fn cells_with_candidates(solver: &LogicalSolver, count: usize) -> Vec<(Position, Vec<Digit>)> {
+    solver
+        .unsolved()
+        .filter(|(_, candidates)| candidates.len() == count)
+        .map(|(pos, candidates)| (pos, candidates.iter().collect()))
+        .collect()
+}
+
+fn eliminate(
+    solver: &LogicalSolver,
+    technique: Technique,
+    cells: Vec<Position>,
+    digits: Vec<Digit>,
+    targets: &[Position],
+    digit: Digit,
+) -> Option<Deduction> {
+    let eliminations: Vec<_> = seen_by_all(targets)
+        .into_iter()
+        .filter(|&pos| solver.candidates(pos).contains(digit))
+        .map(|pos| (pos, digit))
+        .collect();
+
+    if eliminations.is_empty() {
+        return None;
+    }
+
+    Some(Deduction {
+        technique,
+        cells,
+        digits,
+        base: Vec::new(),
+        cover: Vec::new(),
+        chain: Vec::new(),
+        placements: Vec::new(),
+        eliminations,
+    })
+}
+
+/// A pivot with candidates `xy` seeing two pincers `xz` and `yz`, so whichever
+/// the pivot is, one of the pincers is `z`
+pub(super) fn xy_wing(solver: &LogicalSolver) -> Option<Deduction> {
+    let bivalues = cells_with_candidates(solver, 2);
+
+    bivalues.iter().find_map(|(pivot, pivot_digits)| {
+        let (x, y) = (pivot_digits[0], pivot_digits[1]);
+        let pincers: Vec<_> = bivalues
+            .iter()
+            .filter(|(pos, _)| pos != pivot && sees(*pivot, *pos))
+            .collect();
+
+        pincers.iter().find_map(|(a, a_digits)| {
+            if !a_digits.contains(&x) || a_digits.contains(&y) {
+                return None;
+            }
+
+            let z = *a_digits.iter().find(|&&digit| digit != x)?;
+
+            pincers.iter().find_map(|(b, b_digits)| {
+                if !b_digits.contains(&y) || !b_digits.contains(&z) {
+                    return None;
+                }
+
+                eliminate(
+                    solver,
+                    Technique::XYWing,
+                    vec![*pivot, *a, *b],
+                    vec![x, y, z],
+                    &[*a, *b],
+                    z,
+                )
+            })
+        })
+    })
+}
+
+/// Like an [`xy_wing`], but the pivot also has `z` as a candidate, so only
+/// cells seeing all three lose `z`
+pub(super) fn xyz_wing(solver: &LogicalSolver) -> Option<Deduction> {
+    let bivalues = cells_with_candidates(solver, 2);
+
+    cells_with_candidates(solver, 3)
+        .iter()
+        .find_map(|(pivot, pivot_digits)| {
+            let pincers: Vec<_> = bivalues
+                .iter()
+                .filter(|(pos, digits)| {
+                    sees(*pivot, *pos) && digits.iter().all(|digit| pivot_digits.contains(digit))
+                })
+                .collect();
+
+            pincers.iter().find_map(|(a, a_digits)| {
+                pincers.iter().find_map(|(b, b_digits)| {
+                    if a >= b || a_digits == b_digits {
+                        return None;
+                    }
+
+                    let z = *a_digits.iter().find(|digit| b_digits.contains(digit))?;
+
+                    eliminate(
+                        solver,
+                        Technique::XYZWing,
+                        vec![*pivot, *a, *b],
+                        pivot_digits.clone(),
+                        &[*pivot, *a, *b],
+                        z,
+                    )
+                })
+            })
+        })
+}
+
+/// Two cells with the same candidates `xy` joined by a strong link on `x`, so
+/// one of them has to be `y`
+pub(super) fn w_wing(solver: &LogicalSolver) -> Option<Deduction> {
+    let bivalues = cells_with_candidates(solver, 2);
+
+    bivalues.iter().find_map(|(a, digits)| {
+        bivalues.iter().find_map(|(b, b_digits)| {
+            if a >= b || digits != b_digits || sees(*a, *b) {
+                return None;
+            }
+
+            [(digits[0], digits[1]), (digits[1], digits[0])]
+                .iter()
+                .find_map(|&(x, y)| {
+                    houses().find_map(|house| {
+                        let (start, end) = match solver.positions(house, x)[..] {
+                            [start, end] => (start, end),
+                            _ => return None,
+                        };
+
+                        if [start, end].iter().any(|pos| pos == a || pos == b) {
+                            return None;
+                        }
+
+                        if !(sees(start, *a) && sees(end, *b) || sees(start, *b) && sees(end, *a)) {
+                            return None;
+                        }
+
+                        eliminate(
+                            solver,
+                            Technique::WWing,
+                            vec![*a, *b, start, end],
+                            vec![x, y],
+                            &[*a, *b],
+                            y,
+                        )
+                        .map(|deduction| Deduction {
+                            base: vec![house],
+                            ..deduction
+                        })
+                    })
+                })
+        })
+    })
+}
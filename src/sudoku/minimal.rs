@@ -0,0 +1,80 @@
+use super::{Position, Sudoku};
+
+impl Sudoku {
+    /// The puzzle without any of the digits placed while solving it
+    fn givens_only(&self) -> Sudoku {
+        let mut puzzle = Sudoku::new();
+
+        for (pos, cell) in self.all_raw() {
+            if let Some(cell) = cell.filter(|cell| cell.is_given()) {
+                puzzle.set_given(pos, cell.value);
+            }
+        }
+
+        puzzle
+    }
+
+    /// The givens which can be removed on their own without the puzzle losing
+    /// its unique solution, or `None` if it does not have exactly one solution
+    pub fn redundant_givens(&self) -> Option<Vec<Position>> {
+        let mut puzzle = self.givens_only();
+
+        if !puzzle.has_unique_solution() {
+            return None;
+        }
+
+        let givens: Vec<_> = puzzle
+            .all_raw()
+            .filter_map(|(pos, cell)| cell.map(|cell| (pos, cell.value)))
+            .collect();
+
+        let redundant = givens
+            .into_iter()
+            .filter(|&(pos, value)| {
+                puzzle.set(pos, None);
+                let is_redundant = puzzle.has_unique_solution();
+                puzzle.set_given(pos, value);
+
+                is_redundant
+            })
+            .map(|(pos, _)| pos)
+            .collect();
+
+        Some(redundant)
+    }
+
+    /// No given can be removed without the puzzle losing its unique solution
+    pub fn is_minimal(&self) -> bool {
+        self.redundant_givens()
+            .map_or(false, |redundant| redundant.is_empty())
+    }
+
+    /// Removes givens until the puzzle is minimal, leaving the cells in `keep`
+    /// alone, or returns `None` if it does not have exactly one solution
+    ///
+    /// Givens are tried in order, so a puzzle can have other, smaller minimal
+    /// forms.
+    pub fn minimize(&self, keep: &[Position]) -> Option<Sudoku> {
+        let mut puzzle = self.givens_only();
+
+        if !puzzle.has_unique_solution() {
+            return None;
+        }
+
+        let givens: Vec<_> = puzzle
+            .all_raw()
+            .filter(|(pos, _)| !keep.contains(pos))
+            .filter_map(|(pos, cell)| cell.map(|cell| (pos, cell.value)))
+            .collect();
+
+        for (pos, value) in givens {
+            puzzle.set(pos, None);
+
+            if !puzzle.has_unique_solution() {
+                puzzle.set_given(pos, value);
+            }
+        }
+
+        Some(puzzle)
+    }
+}
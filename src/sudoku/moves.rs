@@ -0,0 +1,102 @@
+use std::{
+    convert::TryFrom,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use super::{position::NotationError, Digit, Position};
+
+/// A single change to the board, written `r3c5=7` for placing a digit and
+/// `r3c5<>7` for ruling it out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Move {
+    Place(Position, Digit),
+    Eliminate(Position, Digit),
+}
+
+impl Move {
+    pub fn position(self) -> Position {
+        match self {
+            Move::Place(position, _) | Move::Eliminate(position, _) => position,
+        }
+    }
+
+    pub fn digit(self) -> Digit {
+        match self {
+            Move::Place(_, digit) | Move::Eliminate(_, digit) => digit,
+        }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Move::Place(position, digit) => write!(f, "{}={}", position, u8::from(*digit)),
+            Move::Eliminate(position, digit) => write!(f, "{}<>{}", position, u8::from(*digit)),
+        }
+    }
+}
+
+impl FromStr for Move {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (position, digit, place) = if let Some((position, digit)) = s.split_once("<>") {
+            (position, digit, false)
+        } else if let Some((position, digit)) = s.split_once('=') {
+            (position, digit, true)
+        } else {
+            return Err(NotationError::Malformed(s.trim().to_owned()));
+        };
+
+        let position = position.parse()?;
+        let digit = match digit.trim().as_bytes() {
+            &[digit @ b'1'..=b'9'] => Digit::try_from(digit - b'0').ok(),
+            _ => None,
+        }
+        .ok_or_else(|| NotationError::InvalidDigit(digit.trim().to_owned()))?;
+
+        Ok(if place {
+            Move::Place(position, digit)
+        } else {
+            Move::Eliminate(position, digit)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notation() {
+        let position = Position::new(2, 4).unwrap();
+
+        assert_eq!("r3c5=7".parse(), Ok(Move::Place(position, Digit::Seven)));
+        assert_eq!(
+            " R3C5<>7 ".parse(),
+            Ok(Move::Eliminate(position, Digit::Seven))
+        );
+
+        for digit in ["+7", "07", "0", "10", "", "x"].iter() {
+            assert_eq!(
+                format!("r3c5={}", digit).parse::<Move>(),
+                Err(NotationError::InvalidDigit(digit.to_string()))
+            );
+        }
+
+        for position in ["r+3c5", "rc5", "r3c5c1"].iter() {
+            assert_eq!(
+                format!("{}=7", position).parse::<Move>(),
+                Err(NotationError::Malformed(position.to_string()))
+            );
+        }
+
+        for position in ["r0c5", "r3c05", "r3c10"].iter() {
+            assert_eq!(
+                format!("{}=7", position).parse::<Move>(),
+                Err(NotationError::OutOfRange(position.to_string()))
+            );
+        }
+    }
+}
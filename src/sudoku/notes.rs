@@ -0,0 +1,97 @@
+use super::{peers, Candidates, Digit, Position, Sudoku};
+
+/// Which of a cell's notes a digit is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NoteMode {
+    /// The digits which can only go in a few cells of a house, written in the
+    /// corners
+    Corner,
+    /// The digits a cell can still hold, written across its centre
+    Centre,
+}
+
+/// The player's notes for a cell, kept apart from the candidates of
+/// [`Sudoku::possibilities`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Notes {
+    pub corner: Candidates,
+    pub centre: Candidates,
+}
+
+impl Notes {
+    pub fn marks(self, mode: NoteMode) -> Candidates {
+        match mode {
+            NoteMode::Corner => self.corner,
+            NoteMode::Centre => self.centre,
+        }
+    }
+
+    pub fn marks_mut(&mut self, mode: NoteMode) -> &mut Candidates {
+        match mode {
+            NoteMode::Corner => &mut self.corner,
+            NoteMode::Centre => &mut self.centre,
+        }
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.corner.is_empty() && self.centre.is_empty()
+    }
+
+    fn remove(&mut self, digit: Digit) {
+        self.corner.remove(digit);
+        self.centre.remove(digit);
+    }
+}
+
+impl Sudoku {
+    pub fn notes(&self, position: Position) -> Notes {
+        self.notes[position.index()]
+    }
+
+    pub fn set_notes(&mut self, position: Position, notes: Notes) {
+        self.notes[position.index()] = notes;
+    }
+
+    /// Adds or removes a note, returning whether the cell has it now
+    pub fn toggle_note(&mut self, position: Position, digit: Digit, mode: NoteMode) -> bool {
+        let marks = self.notes[position.index()].marks_mut(mode);
+
+        if marks.remove(digit) {
+            false
+        } else {
+            marks.insert(digit)
+        }
+    }
+
+    pub fn auto_notes(&self) -> bool {
+        self.auto_notes
+    }
+
+    /// With auto-notes on, the notes are filled in with
+    /// [`Sudoku::fill_notes`] and every digit placed, apart from the solver's,
+    /// is removed from the notes of its peers
+    pub fn set_auto_notes(&mut self, enabled: bool) {
+        if enabled && !self.auto_notes {
+            self.fill_notes();
+        }
+
+        self.auto_notes = enabled;
+    }
+
+    /// Replaces the centre notes of every empty cell with its possibilities
+    pub fn fill_notes(&mut self) {
+        for position in Position::all() {
+            if self.get_raw(position).is_none() {
+                self.notes[position.index()].centre = self.possibilities(position);
+            }
+        }
+    }
+
+    /// Removes `digit` from the notes of the peers of `position`, for
+    /// auto-notes
+    pub(super) fn remove_from_peer_notes(&mut self, position: Position, digit: Digit) {
+        for peer in peers(position) {
+            self.notes[peer.index()].remove(digit);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use thiserror::Error;
+
+use super::House;
+
+/// A cell of the board, written `r3c5` for the third row and fifth column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    row: u8,
+    column: u8,
+}
+
+impl Position {
+    /// The cell at the given row and column, counting from 0
+    pub fn new(row: usize, column: usize) -> Option<Self> {
+        if row < 9 && column < 9 {
+            Some(Position {
+                row: row as u8,
+                column: column as u8,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `index` in reading order
+    pub fn from_index(index: usize) -> Option<Self> {
+        if index < 81 {
+            Self::new(index / 9, index % 9)
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `index` in reading order within a block
+    pub fn in_block(block: usize, index: usize) -> Option<Self> {
+        if block < 9 && index < 9 {
+            Self::new((block / 3) * 3 + index / 3, (block % 3) * 3 + index % 3)
+        } else {
+            None
+        }
+    }
+
+    /// Every cell of the board in reading order
+    pub fn all() -> impl Iterator<Item = Position> {
+        (0..81).filter_map(Self::from_index)
+    }
+
+    pub fn row(self) -> usize {
+        self.row as usize
+    }
+
+    pub fn column(self) -> usize {
+        self.column as usize
+    }
+
+    /// The block, numbered left to right, top to bottom
+    pub fn block(self) -> usize {
+        self.column() / 3 + (self.row() / 3) * 3
+    }
+
+    pub fn index(self) -> usize {
+        self.row() * 9 + self.column()
+    }
+
+    pub fn houses(self) -> [House; 3] {
+        House::of(self)
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "r{}c{}", self.row + 1, self.column + 1)
+    }
+}
+
+/// Reads `r3c5`, in either case
+impl FromStr for Position {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        let rest = s
+            .strip_prefix('r')
+            .ok_or_else(|| NotationError::Malformed(s.clone()))?;
+        let (row, column) = rest
+            .split_once('c')
+            .ok_or_else(|| NotationError::Malformed(s.clone()))?;
+
+        let number = |part: &str| match part.as_bytes() {
+            &[digit @ b'1'..=b'9'] => Ok((digit - b'1') as usize),
+            [] => Err(NotationError::Malformed(s.clone())),
+            digits if digits.iter().all(u8::is_ascii_digit) => {
+                Err(NotationError::OutOfRange(s.clone()))
+            }
+            _ => Err(NotationError::Malformed(s.clone())),
+        };
+
+        Ok(Position {
+            row: number(row)? as u8,
+            column: number(column)? as u8,
+        })
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NotationError {
+    #[error("{0:?} is not written like r3c5, r3c5=7 or r3c5<>7")]
+    Malformed(String),
+    #[error("{0} is not on the board")]
+    OutOfRange(String),
+    #[error("{0:?} is not a digit from 1 to 9")]
+    InvalidDigit(String),
+}
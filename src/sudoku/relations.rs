@@ -0,0 +1,104 @@
+use std::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+    ops::{BitOr, BitOrAssign},
+};
+
+use super::{House, Position, Relation};
+
+/// A set of [`Relation`]s, for two cells which can share a block and a line at
+/// the same time
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Relations(u8);
+
+impl Relations {
+    const ALL: [Relation; 3] = [Relation::Block, Relation::Row, Relation::Column];
+
+    pub const fn none() -> Self {
+        Relations(0)
+    }
+
+    pub fn single(relation: Relation) -> Self {
+        Relations(1 << relation as u8)
+    }
+
+    /// Every house `a` and `b` are both in, which is none for a cell and itself
+    pub fn between(a: Position, b: Position) -> Self {
+        if a == b {
+            return Relations::none();
+        }
+
+        let [row, column, block] = House::of(a);
+
+        [block, row, column]
+            .iter()
+            .filter(|house| house.contains(b))
+            .map(|house| house.relation())
+            .collect()
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, relation: Relation) -> bool {
+        self.0 & Self::single(relation).0 != 0
+    }
+
+    /// Adds `relation`, returning whether it was not in the set yet
+    pub fn insert(&mut self, relation: Relation) -> bool {
+        let is_new = !self.contains(relation);
+        self.0 |= Self::single(relation).0;
+        is_new
+    }
+
+    /// The relations in the order they are declared
+    pub fn iter(self) -> impl Iterator<Item = Relation> {
+        Self::ALL
+            .iter()
+            .copied()
+            .filter(move |&relation| self.contains(relation))
+    }
+}
+
+impl Debug for Relations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl From<Relation> for Relations {
+    fn from(relation: Relation) -> Self {
+        Self::single(relation)
+    }
+}
+
+impl FromIterator<Relation> for Relations {
+    fn from_iter<I: IntoIterator<Item = Relation>>(relations: I) -> Self {
+        let mut set = Relations::none();
+
+        for relation in relations {
+            set.insert(relation);
+        }
+
+        set
+    }
+}
+
+impl BitOr for Relations {
+    type Output = Relations;
+
+    fn bitor(self, other: Relations) -> Relations {
+        Relations(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Relations {
+    fn bitor_assign(&mut self, other: Relations) {
+        self.0 |= other.0;
+    }
+}
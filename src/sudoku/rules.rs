@@ -0,0 +1,92 @@
+use thiserror::Error;
+
+use super::{peers, CellOrigin, Digit, Position, Sudoku};
+
+/// How strictly [`Sudoku::try_set`] checks a placement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rules {
+    /// Refuse digits a peer already has
+    Strict,
+    /// Allow conflicting digits, leaving the board invalid until one of them
+    /// is removed
+    Lenient,
+}
+
+/// Why a cell could not be changed
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    #[error("{0} is a given and can not be changed")]
+    Given(Position),
+    #[error("{0:?} digits can not be placed as a move")]
+    Origin(CellOrigin),
+    #[error("{} can not go in {position}, {peer} already has it", u8::from(*.digit))]
+    Conflict {
+        position: Position,
+        digit: Digit,
+        /// The first cell seeing `position` which holds `digit`
+        peer: Position,
+    },
+}
+
+impl Sudoku {
+    /// Places `digit` on behalf of `origin` as the next move, unless the cell
+    /// is a given or, with [`Rules::Strict`], one of its peers already has the
+    /// digit. Givens and imported digits are never moves.
+    pub fn try_set(
+        &mut self,
+        position: Position,
+        digit: Digit,
+        origin: CellOrigin,
+        rules: Rules,
+    ) -> Result<(), MoveError> {
+        if let CellOrigin::Given | CellOrigin::Imported = origin {
+            return Err(MoveError::Origin(origin));
+        }
+
+        self.check_set(position, digit, rules)?;
+        self.play(position, Some(digit), origin);
+
+        Ok(())
+    }
+
+    /// The checks of [`Sudoku::try_set`], for callers which change the board
+    /// without making a move
+    pub(crate) fn check_set(
+        &self,
+        position: Position,
+        digit: Digit,
+        rules: Rules,
+    ) -> Result<(), MoveError> {
+        self.check_given(position)?;
+
+        if rules == Rules::Strict {
+            let peer = peers(position)
+                .find(|&peer| self.get_raw(peer).map_or(false, |cell| cell.value == digit));
+
+            if let Some(peer) = peer {
+                return Err(MoveError::Conflict {
+                    position,
+                    digit,
+                    peer,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Empties the cell as the next move, unless it is a given
+    pub fn try_clear(&mut self, position: Position) -> Result<(), MoveError> {
+        self.check_given(position)?;
+        self.play(position, None, CellOrigin::Player);
+
+        Ok(())
+    }
+
+    pub(crate) fn check_given(&self, position: Position) -> Result<(), MoveError> {
+        match self.get_raw(position) {
+            Some(cell) if cell.is_given() => Err(MoveError::Given(position)),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use super::{Candidates, CellValue, Digit, House, Position, Rules, SolveStatus, Sudoku};
+
+/// A single action taken by the [`Solver`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// A digit was guessed for the cell with the fewest pencil marks, and the
+    /// singles following from it were filled in
+    Placed(Position, Digit),
+    /// A previous guess was replaced by one that had not been tried yet
+    Retried(Position, Digit),
+    /// A previous guess ran out of alternatives and was cleared, along with
+    /// the singles it forced
+    Backtracked(Position),
+    /// Every cell has a digit
+    Solved,
+    /// Backtracked past the first guess, the board has no solution
+    Unsolvable,
+}
+
+/// Depth first backtracking solver, always guessing in the cell with the fewest
+/// remaining possibilities
+///
+/// After every guess the naked and hidden singles it leads to are placed too,
+/// so that dead ends are found without guessing any further
+///
+/// The solver works one [`Step`] at a time so that callers are able to show
+/// its progress
+#[derive(Debug, Default, Clone)]
+pub struct Solver {
+    moves: Vec<Guess>,
+    backtracking: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Guess {
+    pos: Position,
+    tried: Candidates,
+    /// The cells filled in by propagating the guess
+    forced: Vec<Position>,
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn step(&mut self, sudoku: &mut Sudoku) -> Step {
+        if self.backtracking {
+            return self.backtrack(sudoku);
+        }
+
+        // A conflict the board came with can never be resolved by guessing
+        if self.moves.is_empty() && sudoku.status() == SolveStatus::Invalid {
+            return Step::Unsolvable;
+        }
+
+        let smallest = sudoku
+            .all()
+            .filter_map(|(pos, cell)| match cell {
+                CellValue::Unknown(options) => Some((pos, options)),
+                CellValue::Known(_) => None,
+            })
+            .min_by_key(|(_, marks)| marks.len())
+            .map(|(pos, marks)| (pos, marks.first()));
+
+        match smallest {
+            Some((pos, Some(digit))) => {
+                let mut guess = Guess {
+                    pos,
+                    tried: Candidates::single(digit),
+                    forced: Vec::new(),
+                };
+
+                sudoku
+                    .check_set(pos, digit, Rules::Strict)
+                    .expect("guesses are always candidates of an empty cell");
+                sudoku.set(pos, Some(digit));
+                self.backtracking = !sudoku.propagate(&mut guess.forced);
+                self.moves.push(guess);
+
+                Step::Placed(pos, digit)
+            }
+            Some((_, None)) => {
+                // Ran into a cell with 0 pencil marks
+                self.backtracking = true;
+
+                self.backtrack(sudoku)
+            }
+            None if sudoku.status() == SolveStatus::Solved => Step::Solved,
+            None => {
+                // Every cell is filled, but the board came with a conflict
+                self.backtracking = true;
+
+                self.backtrack(sudoku)
+            }
+        }
+    }
+
+    fn backtrack(&mut self, sudoku: &mut Sudoku) -> Step {
+        match self.moves.pop() {
+            Some(mut guess) => {
+                sudoku.undo(&mut guess.forced);
+
+                let possibilities = sudoku.possibilities(guess.pos);
+
+                if let Some(other_possibility) = (possibilities - guess.tried).first() {
+                    sudoku
+                        .check_set(guess.pos, other_possibility, Rules::Strict)
+                        .expect("guesses are always candidates of an empty cell");
+                    sudoku.set(guess.pos, Some(other_possibility));
+
+                    guess.tried.insert(other_possibility);
+                    self.backtracking = !sudoku.propagate(&mut guess.forced);
+                    let pos = guess.pos;
+                    self.moves.push(guess);
+
+                    Step::Retried(pos, other_possibility)
+                } else {
+                    sudoku
+                        .check_given(guess.pos)
+                        .expect("guesses are never made in givens");
+                    sudoku.set(guess.pos, None);
+
+                    Step::Backtracked(guess.pos)
+                }
+            }
+            None => Step::Unsolvable,
+        }
+    }
+}
+
+/// A way of searching for the solutions of a board, so callers can pick the
+/// one which suits them
+pub trait Backend {
+    fn solve(&self, sudoku: &Sudoku) -> Option<Sudoku>;
+
+    /// Counts the solutions of the board, stopping once `limit` have been found
+    fn count_solutions(&self, sudoku: &Sudoku, limit: usize) -> usize;
+
+    fn has_unique_solution(&self, sudoku: &Sudoku) -> bool {
+        self.count_solutions(sudoku, 2) == 1
+    }
+}
+
+/// The depth first search of the [`Solver`], used by [`Sudoku::solve`] and
+/// [`Sudoku::count_solutions`]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Backtracking;
+
+impl Backend for Backtracking {
+    fn solve(&self, sudoku: &Sudoku) -> Option<Sudoku> {
+        sudoku.solve()
+    }
+
+    fn count_solutions(&self, sudoku: &Sudoku, limit: usize) -> usize {
+        sudoku.count_solutions(limit)
+    }
+}
+
+impl Sudoku {
+    pub fn solve(&self) -> Option<Sudoku> {
+        let mut solution = self.clone();
+
+        if solution.solve_in_place() {
+            Some(solution)
+        } else {
+            None
+        }
+    }
+
+    pub fn solve_in_place(&mut self) -> bool {
+        let mut solver = Solver::new();
+
+        loop {
+            match solver.step(self) {
+                Step::Solved => return true,
+                Step::Unsolvable => return false,
+                Step::Placed(..) | Step::Retried(..) | Step::Backtracked(..) => {}
+            }
+        }
+    }
+
+    /// Counts the solutions of the board, stopping once `limit` have been found
+    ///
+    /// A proper puzzle has exactly one solution, so `count_solutions(2)` is
+    /// enough to tell proper, ambiguous and unsolvable boards apart
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        if self
+            .all_raw()
+            .any(|(pos, _)| self.cell_status(pos) == SolveStatus::Invalid)
+        {
+            return 0;
+        }
+
+        let mut sudoku = self.clone();
+        let mut count = 0;
+
+        sudoku.count_solutions_from(limit, &mut count);
+
+        count
+    }
+
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    fn count_solutions_from(&mut self, limit: usize, count: &mut usize) {
+        if *count >= limit {
+            return;
+        }
+
+        let smallest = self
+            .all()
+            .filter_map(|(pos, cell)| match cell {
+                CellValue::Unknown(options) => Some((pos, options)),
+                CellValue::Known(_) => None,
+            })
+            .min_by_key(|(_, marks)| marks.len());
+
+        match smallest {
+            Some((pos, options)) => {
+                let mut forced = Vec::new();
+
+                for digit in options {
+                    self.set(pos, Some(digit));
+
+                    if self.propagate(&mut forced) {
+                        self.count_solutions_from(limit, count);
+                    }
+                    self.undo(&mut forced);
+
+                    if *count >= limit {
+                        break;
+                    }
+                }
+
+                self.set(pos, None);
+            }
+            None => *count += 1,
+        }
+    }
+
+    /// Places every naked and hidden single until there are none left, adding
+    /// the cells filled in to `forced`
+    ///
+    /// Returns `false` as soon as an empty cell has no candidates, or a digit
+    /// has no place left in a house
+    fn propagate(&mut self, forced: &mut Vec<Position>) -> bool {
+        loop {
+            let mut progress = false;
+
+            for house in House::all() {
+                let mut once = Candidates::none();
+                let mut more = Candidates::none();
+                let mut empty = 0;
+
+                for pos in house.cells() {
+                    if self.get_raw(pos).is_some() {
+                        continue;
+                    }
+
+                    empty += 1;
+
+                    let candidates = self.possibilities(pos);
+
+                    match candidates.len() {
+                        0 => return false,
+                        1 => {
+                            self.set(pos, candidates.first());
+                            forced.push(pos);
+                            progress = true;
+
+                            continue;
+                        }
+                        _ => {}
+                    }
+
+                    more |= once & candidates;
+                    once |= candidates;
+                }
+
+                let missing = !(once | self.houses[house.id()]);
+                if !missing.is_empty() {
+                    return false;
+                }
+
+                // A digit placed twice leaves more digits to place than cells
+                if (!self.houses[house.id()]).len() > empty {
+                    return false;
+                }
+
+                for digit in once - more {
+                    if let Some(pos) = house.cells().find(|&pos| {
+                        self.get_raw(pos).is_none() && self.possibilities(pos).contains(digit)
+                    }) {
+                        self.set(pos, Some(digit));
+                        forced.push(pos);
+                        progress = true;
+                    }
+                }
+            }
+
+            if !progress {
+                return true;
+            }
+        }
+    }
+
+    /// Clears the cells filled in by [`Sudoku::propagate`]
+    fn undo(&mut self, forced: &mut Vec<Position>) {
+        for pos in forced.drain(..).rev() {
+            self.set(pos, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sudoku::{CellOrigin, Rules};
+
+    #[test]
+    fn conflicting_board_is_unsolvable() {
+        let mut sudoku = Sudoku::new();
+
+        for column in [0, 4] {
+            sudoku
+                .try_set(
+                    Position::new(0, column).unwrap(),
+                    Digit::Five,
+                    CellOrigin::Player,
+                    Rules::Lenient,
+                )
+                .unwrap();
+        }
+
+        assert_eq!(sudoku.status(), SolveStatus::Invalid);
+        assert_eq!(Solver::new().step(&mut sudoku.clone()), Step::Unsolvable);
+        assert!(sudoku.solve().is_none());
+        assert!(!sudoku.clone().solve_in_place());
+        assert_eq!(sudoku.count_solutions(2), 0);
+    }
+}